@@ -0,0 +1,102 @@
+//! Poseidon2 hashing hot paths: the sponge, the batch-merge `h2` (by value
+//! and by reference), and a 1024-leaf canonical root.
+
+use criterion::{BenchmarkId, Criterion, Throughput, black_box, criterion_group, criterion_main};
+use usernode_circuits::batch::canonical_root_even;
+use usernode_circuits::bn254::Field;
+use usernode_circuits::poseidon2::{h2, h2_ref, hash_fields};
+
+const SPONGE_INPUTS: u128 = 10;
+const H2_PAIRS: u128 = 10_000;
+const ROOT_LEAVES: u128 = 1024;
+
+fn fields(count: u128) -> Vec<Field> {
+    (1..=count).map(Field::from).collect()
+}
+
+fn bench_hash_fields(c: &mut Criterion) {
+    let inputs = fields(SPONGE_INPUTS);
+    let mut group = c.benchmark_group("hash_fields");
+    group.throughput(Throughput::Elements(1));
+    group.bench_with_input(
+        BenchmarkId::from_parameter(inputs.len()),
+        &inputs,
+        |b, inputs| b.iter(|| hash_fields(black_box(inputs))),
+    );
+    group.finish();
+}
+
+fn bench_h2(c: &mut Criterion) {
+    let pairs: Vec<(Field, Field)> = (1..=H2_PAIRS)
+        .map(|i| (Field::from(i), Field::from(i.saturating_add(H2_PAIRS))))
+        .collect();
+    let mut group = c.benchmark_group("h2");
+    group.throughput(Throughput::Elements(H2_PAIRS as u64));
+    group.bench_with_input(
+        BenchmarkId::from_parameter(pairs.len()),
+        &pairs,
+        |b, pairs| {
+            b.iter(|| {
+                for (left, right) in pairs {
+                    black_box(h2(*left, *right));
+                }
+            })
+        },
+    );
+    group.finish();
+}
+
+fn bench_h2_call_patterns(c: &mut Criterion) {
+    let leaves = fields(ROOT_LEAVES);
+    let mut group = c.benchmark_group("h2_call_pattern");
+    group.throughput(Throughput::Elements(ROOT_LEAVES.saturating_div(2) as u64));
+    group.bench_with_input(
+        BenchmarkId::new("by_value", leaves.len()),
+        &leaves,
+        |b, leaves| {
+            b.iter(|| {
+                for pair in leaves.chunks_exact(2) {
+                    if let [left, right] = pair {
+                        black_box(h2(*left, *right));
+                    }
+                }
+            })
+        },
+    );
+    group.bench_with_input(
+        BenchmarkId::new("by_ref", leaves.len()),
+        &leaves,
+        |b, leaves| {
+            b.iter(|| {
+                for pair in leaves.chunks_exact(2) {
+                    if let [left, right] = pair {
+                        black_box(h2_ref(left, right));
+                    }
+                }
+            })
+        },
+    );
+    group.finish();
+}
+
+fn bench_canonical_root_even(c: &mut Criterion) {
+    let leaves = fields(ROOT_LEAVES);
+    let mut group = c.benchmark_group("canonical_root_even");
+    // Folding n leaves down to a root takes n - 1 `h2` calls.
+    group.throughput(Throughput::Elements(ROOT_LEAVES.saturating_sub(1) as u64));
+    group.bench_with_input(
+        BenchmarkId::from_parameter(leaves.len()),
+        &leaves,
+        |b, leaves| b.iter(|| canonical_root_even(black_box(leaves))),
+    );
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_hash_fields,
+    bench_h2,
+    bench_h2_call_patterns,
+    bench_canonical_root_even
+);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+//! A single `utxo_spend` proof through `prover::prove`.
+//!
+//! The request is prepared once up front, so each iteration measures witness
+//! solving plus Barretenberg proving only.
+
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use usernode_circuits::bn254::Field;
+use usernode_circuits::keys::Keypair;
+use usernode_circuits::prover::{encode_abi_inputs, get_circuit, init_default_circuits, prove};
+use usernode_circuits::tx::{RequestOptions, SpendRequest, prepare_spend};
+use usernode_circuits::types::{Asset, SchnorrPublicKey, SpendInput, Utxo};
+
+fn spend_privates() -> Vec<usernode_circuits::CircuitFieldElement> {
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (pk_x, pk_y) = signer.public_key_xy();
+    let utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(pk_x),
+        salt: Field::from(42u128),
+    };
+    let prepared = prepare_spend(&SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(utxo, SchnorrPublicKey::new(pk_x, pk_y)),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    })
+    .expect("prepare spend");
+    let entry = get_circuit("utxo_spend").expect("spend circuit");
+    encode_abi_inputs(&entry.abi, &prepared.abi_inputs).expect("encode spend inputs")
+}
+
+fn bench_prove_spend(c: &mut Criterion) {
+    init_default_circuits().expect("init embedded circuits");
+    let privates = spend_privates();
+
+    let mut group = c.benchmark_group("prove");
+    group.sample_size(10);
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("utxo_spend", |b| {
+        b.iter(|| prove("utxo_spend", black_box(&privates)).expect("spend proof"))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_prove_spend);
+criterion_main!(benches);
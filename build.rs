@@ -0,0 +1,43 @@
+//! Compute SHA256 digests of the embedded ACIR blobs at compile time.
+//!
+//! The digests are written to `$OUT_DIR/acir_hashes.rs` and pulled into
+//! `src/artifacts.rs` so every `EmbeddedCircuit` carries the hash of the bytes
+//! it embeds. A missing ACIR file aborts the build instead of silently
+//! producing a catalog without that circuit.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+
+const CIRCUITS: &[&str] = &["utxo_spend", "utxo_merge"];
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"));
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR"));
+
+    let mut generated = String::new();
+    for name in CIRCUITS {
+        let acir_path = manifest_dir.join("artifacts").join(format!("{name}.acir"));
+        println!("cargo:rerun-if-changed={}", acir_path.display());
+        let acir = fs::read(&acir_path).unwrap_or_else(|err| {
+            panic!(
+                "missing embedded ACIR for {name} at {}: {err}",
+                acir_path.display()
+            )
+        });
+        let digest = Sha256::digest(&acir);
+        let _ = writeln!(
+            generated,
+            "pub(crate) const {}_ACIR_SHA256: [u8; 32] = {:?};",
+            name.to_uppercase(),
+            digest.as_slice()
+        );
+    }
+
+    let out_path = out_dir.join("acir_hashes.rs");
+    fs::write(&out_path, generated)
+        .unwrap_or_else(|err| panic!("writing {}: {err}", out_path.display()));
+}
@@ -0,0 +1,46 @@
+#ifndef USERNODE_CIRCUITS_H
+#define USERNODE_CIRCUITS_H
+
+/* Generated by cbindgen from src/ffi.rs; do not edit by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+/**
+ * Configure the CRS directory (when non-null) and load the embedded circuits.
+ *
+ * Returns `0` on success and `-1` on error.
+ *
+ * # Safety
+ *
+ * `crs_dir` must be null or point to a NUL-terminated UTF-8 string.
+ */
+int usernode_init(const char *crs_dir);
+
+/**
+ * Verify `proof` against the registered circuit `circuit_name`.
+ *
+ * Returns `1` for a valid proof, `0` for an invalid one, and `-1` on error
+ * (unknown circuit, bad arguments, or a Barretenberg failure).
+ *
+ * # Safety
+ *
+ * `circuit_name` must point to a NUL-terminated UTF-8 string and `proof` must
+ * be valid for reads of `proof_len` bytes.
+ */
+int usernode_verify(const char *circuit_name, const uint8_t *proof, size_t proof_len);
+
+/**
+ * Poseidon2-hash `inputs_len / 32` big-endian field elements into `out`.
+ *
+ * Returns `0` on success and `-1` if `inputs_len` is not a multiple of 32 or
+ * a pointer is null.
+ *
+ * # Safety
+ *
+ * `inputs` must be valid for reads of `inputs_len` bytes and `out` must be
+ * valid for writes of 32 bytes.
+ */
+int usernode_hash_fields(const uint8_t *inputs, size_t inputs_len, uint8_t *out);
+
+#endif /* USERNODE_CIRCUITS_H */
@@ -1,23 +1,58 @@
+use sha2::{Digest, Sha256};
+
+include!(concat!(env!("OUT_DIR"), "/acir_hashes.rs"));
+
 pub struct EmbeddedCircuit {
     pub name: &'static str,
+    /// Semver tag of the Noir circuit source the blobs were generated from.
+    pub version: &'static str,
     pub acir: &'static [u8],
+    /// SHA256 of `acir`, computed by `build.rs` when the crate is compiled.
+    pub acir_hash: [u8; 32],
     pub vk: &'static [u8],
     pub abi_json: &'static str,
+    /// One-line summary read from the companion `<name>.description.txt`.
+    pub description: &'static str,
+}
+
+impl EmbeddedCircuit {
+    /// Recompute the ACIR digest and compare it with the build-time `acir_hash`.
+    pub fn verify_checksum(&self) -> anyhow::Result<()> {
+        verify_acir_checksum(self.name, self.acir, &self.acir_hash)
+    }
+}
+
+/// Fail if `sha256(acir)` does not equal `expected`.
+pub fn verify_acir_checksum(name: &str, acir: &[u8], expected: &[u8; 32]) -> anyhow::Result<()> {
+    let actual: [u8; 32] = Sha256::digest(acir).into();
+    anyhow::ensure!(
+        actual == *expected,
+        "ACIR checksum mismatch for {name}: expected {}, got {}",
+        crate::hex_fmt::prefixed(expected),
+        crate::hex_fmt::prefixed(&actual)
+    );
+    Ok(())
 }
 
 pub fn embedded() -> &'static [EmbeddedCircuit] {
     static CIRCUITS: &[EmbeddedCircuit] = &[
         EmbeddedCircuit {
             name: "utxo_spend",
+            version: "1.0.0",
             acir: include_bytes!("../artifacts/utxo_spend.acir"),
+            acir_hash: UTXO_SPEND_ACIR_SHA256,
             vk: include_bytes!("../artifacts/utxo_spend.vk"),
             abi_json: include_str!("../artifacts/utxo_spend.abi.json"),
+            description: include_str!("../artifacts/utxo_spend.description.txt").trim_ascii_end(),
         },
         EmbeddedCircuit {
             name: "utxo_merge",
+            version: "1.0.0",
             acir: include_bytes!("../artifacts/utxo_merge.acir"),
+            acir_hash: UTXO_MERGE_ACIR_SHA256,
             vk: include_bytes!("../artifacts/utxo_merge.vk"),
             abi_json: include_str!("../artifacts/utxo_merge.abi.json"),
+            description: include_str!("../artifacts/utxo_merge.description.txt").trim_ascii_end(),
         },
     ];
     CIRCUITS
@@ -4,15 +4,70 @@ use std::sync::{Mutex, OnceLock};
 pub(crate) static BB_GUARD: OnceLock<Mutex<()>> = OnceLock::new();
 
 /// Execute `f` while holding the Barretenberg mutex.
-pub(crate) fn with_bb_lock<F, T>(f: F) -> T
+///
+/// Downstream code that calls Barretenberg directly (for example
+/// `aztec_barretenberg_rs::schnorr_blake2s_sign`) should go through this so it
+/// serialises with the crate's own proving and verification calls.
+///
+/// The lock is not reentrant: `f` must not call back into anything that takes
+/// it again, which includes every proving, verification, and catalog
+/// registration function in this crate. Doing so deadlocks the calling thread.
+pub fn with_bb_lock<F, T>(f: F) -> T
 where
     F: FnOnce() -> T,
 {
+    #[cfg(feature = "metrics")]
+    let started = std::time::Instant::now();
     let guard = BB_GUARD
         .get_or_init(|| Mutex::new(()))
         .lock()
         .expect("barretenberg mutex poisoned");
+    #[cfg(feature = "metrics")]
+    metrics::record_acquisition(started.elapsed());
     let result = f();
     drop(guard);
     result
 }
+
+#[cfg(feature = "metrics")]
+pub use metrics::{LockStats, lock_stats, reset_lock_stats};
+
+/// Contention counters for `BB_GUARD`, compiled only with the `metrics` feature.
+#[cfg(feature = "metrics")]
+mod metrics {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+
+    static ACQUISITIONS: AtomicU64 = AtomicU64::new(0);
+    static TOTAL_WAIT_NS: AtomicU64 = AtomicU64::new(0);
+
+    /// Snapshot of how often the Barretenberg lock was taken and how long
+    /// callers waited for it.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct LockStats {
+        /// Number of completed lock acquisitions.
+        pub acquisitions: u64,
+        /// Cumulative time spent between calling `lock()` and acquiring it.
+        pub total_wait_ns: u64,
+    }
+
+    pub(super) fn record_acquisition(waited: Duration) {
+        let waited_ns = u64::try_from(waited.as_nanos()).unwrap_or(u64::MAX);
+        ACQUISITIONS.fetch_add(1, Ordering::Relaxed);
+        TOTAL_WAIT_NS.fetch_add(waited_ns, Ordering::Relaxed);
+    }
+
+    /// Read the current lock counters.
+    pub fn lock_stats() -> LockStats {
+        LockStats {
+            acquisitions: ACQUISITIONS.load(Ordering::Relaxed),
+            total_wait_ns: TOTAL_WAIT_NS.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Reset both lock counters to zero.
+    pub fn reset_lock_stats() {
+        ACQUISITIONS.store(0, Ordering::Relaxed);
+        TOTAL_WAIT_NS.store(0, Ordering::Relaxed);
+    }
+}
@@ -5,9 +5,12 @@
 //! The focus is on documenting how leaf hashes flow into the manifest hash and
 //! the pairwise Poseidon2 folding used to derive batch roots.
 
+use std::collections::{HashMap, HashSet};
+
 use crate::bn254::Field;
-use crate::poseidon2::{h2, hash_manifest, hash_merge_leaf, hash_spend_leaf};
-use crate::types::{MergeTx, SpendTx};
+use crate::merkle::{IncrementalMerkleTree, MAX_TREE_DEPTH};
+use crate::poseidon2::{h2, h2_batch, hash_manifest_v1, hash_merge_leaf, hash_spend_leaf};
+use crate::types::{MergeTx, SpendOutputCommits, SpendTx};
 
 /// Hash binding for a single transaction leaf (either spend or merge).
 #[derive(Clone, Debug)]
@@ -53,7 +56,19 @@ impl BindingBlock {
     /// Poseidon2 hash covering the ordered leaves, block id, and root.
     pub fn manifest_hash(&self) -> Field {
         let hashes: Vec<Field> = self.leaves.iter().map(|l| l.leaf_hash).collect();
-        hash_manifest(self.block_id, self.acceptance_root, &hashes)
+        hash_manifest_v1(self.block_id, self.acceptance_root, &hashes)
+    }
+
+    /// Iterate over the included leaves in block order.
+    pub fn iter_leaves(&self) -> impl Iterator<Item = &BindingLeaf> {
+        self.leaves.iter()
+    }
+
+    /// Parallel iterator over the included leaves (requires the `rayon` feature).
+    #[cfg(feature = "rayon")]
+    pub fn par_iter_leaves(&self) -> impl rayon::iter::ParallelIterator<Item = &BindingLeaf> {
+        use rayon::iter::IntoParallelRefIterator;
+        self.leaves.par_iter()
     }
 
     /// Canonical pairwise Poseidon2 root of the even-length leaf sequence.
@@ -66,48 +81,167 @@ impl BindingBlock {
                 .as_slice(),
         )
     }
+
+    /// Batch root of the included leaves.
+    ///
+    /// The planning helpers always leave an even number of leaves in `leaves`,
+    /// moving any odd one out to `deferred`. The only expected error is therefore an
+    /// empty block; an odd count means the block was assembled by hand and
+    /// breaks that invariant.
+    pub fn batch_root(&self) -> anyhow::Result<Field> {
+        anyhow::ensure!(
+            !self.leaves.is_empty(),
+            "block {} has no leaves",
+            self.block_id
+        );
+        anyhow::ensure!(
+            self.leaves.len() % 2 == 0,
+            "block {} has an odd leaf count ({}); planned blocks are always even",
+            self.block_id,
+            self.leaves.len()
+        );
+        self.canonical_root_even()
+            .ok_or_else(|| anyhow::anyhow!("block {} has no batch root", self.block_id))
+    }
+
+    /// Batch root of the included leaves, or `None` for an empty block.
+    ///
+    /// Also `None` for an odd leaf count, which planned blocks never have; see
+    /// [`batch_root`](Self::batch_root).
+    pub fn try_batch_root(&self) -> Option<Field> {
+        self.canonical_root_even()
+    }
+
+    /// Check every included leaf against the record at the same position.
+    ///
+    /// Returns the indices whose stored `leaf_hash` differs from
+    /// `record.recompute_leaf_hash()`. When the lengths differ the records
+    /// cannot be lined up, so every index is reported.
+    pub fn verify_leaf_hashes(&self, records: &[LeafRecord]) -> Result<(), Vec<usize>> {
+        if records.len() != self.leaves.len() {
+            return Err((0..self.leaves.len().max(records.len())).collect());
+        }
+        let mismatched: Vec<usize> = self
+            .leaves
+            .iter()
+            .zip(records)
+            .enumerate()
+            .filter(|(_, (leaf, record))| leaf.leaf_hash != record.recompute_leaf_hash())
+            .map(|(idx, _)| idx)
+            .collect();
+        if mismatched.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatched)
+        }
+    }
 }
 
-/// Build a binding block from an already ordered list of leaves.
+/// Split ordered candidates into an even-length block batch and the overflow.
 ///
-/// The function enforces the “pair completeness” policy from the node by
-/// moving the last leaf to `deferred` when the input length is odd. The even
-/// prefix is used to compute the manifest hash and batch root.
+/// The batch is the longest even prefix holding at most `max_leaves` leaves;
+/// everything after it, in order, is returned as overflow for a later block.
+/// Pass `usize::MAX` to apply only the “pair completeness” policy, in which
+/// case the overflow is at most the single odd tail leaf.
+pub fn split_candidates_for_block(
+    mut candidates: Vec<BindingLeaf>,
+    max_leaves: usize,
+) -> (Vec<BindingLeaf>, Vec<BindingLeaf>) {
+    let batch_len = candidates.len().min(max_leaves) & !1;
+    let overflow = candidates.split_off(batch_len);
+    (candidates, overflow)
+}
+
+/// Build a binding block from an already ordered, even-length list of leaves.
+///
+/// Sizing is the caller's concern: run the list through
+/// [`split_candidates_for_block`] first. The leaves are used as-is for the
+/// manifest hash and batch root, and `deferred` is left empty. Fails on an
+/// odd list, which would have no batch root.
 pub fn plan_block(
     block_id: u64,
     acceptance_root: Field,
-    mut leaves: Vec<BindingLeaf>,
+    leaves: Vec<BindingLeaf>,
+) -> anyhow::Result<BindingBlock> {
+    anyhow::ensure!(
+        leaves.len() % 2 == 0,
+        "plan_block expects an even leaf count, got {}",
+        leaves.len()
+    );
+    Ok(BindingBlock {
+        block_id,
+        acceptance_root,
+        leaves,
+        deferred: None,
+    })
+}
+
+/// Plan a block from every leaf, deferring the last one when the count is odd.
+fn plan_pair_complete_block(
+    block_id: u64,
+    acceptance_root: Field,
+    leaves: Vec<BindingLeaf>,
 ) -> BindingBlock {
-    let deferred = if leaves.len() % 2 == 1 {
-        leaves.pop()
-    } else {
-        None
-    };
+    let (even, mut overflow) = split_candidates_for_block(leaves, usize::MAX);
     BindingBlock {
         block_id,
         acceptance_root,
-        leaves,
-        deferred,
+        leaves: even,
+        deferred: overflow.pop(),
     }
 }
 
-#[derive(Clone, Debug)]
+/// Plan a pair-complete block for a UTXO tree of depth `tree_depth`.
+///
+/// An odd tail leaf is moved to `deferred`, as in
+/// [`plan_block_from_candidates`]. Fails when `tree_depth` exceeds [`MAX_TREE_DEPTH`] or when the planned
+/// leaves would not fit in an empty depth-`tree_depth` tree; [`compute_new_root`]
+/// checks the space left in a partly filled one. Use
+/// [`catalog::utxo_tree_depth`](crate::catalog::utxo_tree_depth) for the
+/// configured depth.
+// The shift is bounded by the `MAX_TREE_DEPTH` check.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn plan_block_with_depth(
+    block_id: u64,
+    acceptance_root: Field,
+    leaves: Vec<BindingLeaf>,
+    tree_depth: u32,
+) -> anyhow::Result<BindingBlock> {
+    anyhow::ensure!(
+        tree_depth as usize <= MAX_TREE_DEPTH,
+        "tree depth {tree_depth} exceeds the maximum of {MAX_TREE_DEPTH}"
+    );
+    let block = plan_pair_complete_block(block_id, acceptance_root, leaves);
+    let capacity = 1u64 << tree_depth;
+    anyhow::ensure!(
+        block.leaves.len() as u64 <= capacity,
+        "{} leaves do not fit in a depth-{tree_depth} tree",
+        block.leaves.len()
+    );
+    Ok(block)
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct CandidateLeaf {
     /// Caller-chosen identifier for traceability.
     pub leaf_id: Vec<u8>,
     /// Declared leaf hash to be validated.
+    #[serde(with = "crate::hex_fmt::field")]
     pub leaf_hash: Field,
     /// Arrival timestamp used as the primary sorting key.
     pub arrival_time_ns: u64,
     /// Publisher identifier used as tie-breaker.
     pub publisher_id: [u8; 32],
+    /// Submission sequence number; final tie-breaker for otherwise equal candidates.
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 /// Deterministically order candidates and build a pair-complete block.
 ///
-/// Sorting uses `(arrival_time, leaf_hash, publisher_id)` so the outcome is
-/// stable across runs. The resulting block mirrors `plan_block` after the
-/// ordering step.
+/// Sorting uses `(arrival_time, leaf_hash, publisher_id, sequence)` so the
+/// outcome is stable across runs. An odd tail leaf is moved to `deferred`;
+/// the even prefix goes through [`plan_block`].
 pub fn plan_block_from_candidates(
     block_id: u64,
     acceptance_root: Field,
@@ -118,6 +252,7 @@ pub fn plan_block_from_candidates(
             .cmp(&b.arrival_time_ns)
             .then_with(|| field_cmp(&a.leaf_hash, &b.leaf_hash))
             .then_with(|| a.publisher_id.cmp(&b.publisher_id))
+            .then_with(|| a.sequence.cmp(&b.sequence))
     });
     let leaves: Vec<BindingLeaf> = candidates
         .into_iter()
@@ -126,27 +261,72 @@ pub fn plan_block_from_candidates(
             leaf_hash: c.leaf_hash,
         })
         .collect();
-    plan_block(block_id, acceptance_root, leaves)
+    plan_pair_complete_block(block_id, acceptance_root, leaves)
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum LeafRecord {
     Spend {
+        #[serde(with = "crate::hex_fmt::field")]
         in_commit: Field,
+        #[serde(with = "crate::hex_fmt::field")]
         out_commit0: Field,
+        #[serde(with = "crate::hex_fmt::field")]
         out_commit1: Field,
+        #[serde(with = "crate::hex_fmt::field")]
         transfer_token: Field,
+        #[serde(with = "crate::hex_fmt::field")]
         transfer_amount: Field,
+        #[serde(with = "crate::hex_fmt::field")]
         fee_amount: Field,
     },
     Merge {
+        #[serde(with = "crate::hex_fmt::field")]
         in_commit0: Field,
+        #[serde(with = "crate::hex_fmt::field")]
         in_commit1: Field,
+        #[serde(with = "crate::hex_fmt::field")]
         out_commit: Field,
     },
 }
 
 impl LeafRecord {
+    /// Build the `Spend` record matching the leaf hash enforced for `tx`.
+    pub fn from_spend_tx(tx: &SpendTx) -> Self {
+        let SpendOutputCommits {
+            receiver: out_commit0,
+            remainder: out_commit1,
+        } = tx.expected_out_commits;
+        LeafRecord::Spend {
+            in_commit: tx.input.utxo.commitment(),
+            out_commit0,
+            out_commit1,
+            transfer_token: tx.transfer_token,
+            transfer_amount: tx.transfer_amount,
+            fee_amount: tx.fee_amount,
+        }
+    }
+
+    /// Build the `Merge` record matching the leaf hash enforced for `tx`.
+    pub fn from_merge_tx(tx: &MergeTx) -> Self {
+        let [in0, in1] = &tx.inputs;
+        LeafRecord::Merge {
+            in_commit0: in0.utxo.commitment(),
+            in_commit1: in1.utxo.commitment(),
+            out_commit: tx.expected_out_commit.field(),
+        }
+    }
+
+    /// Serialise the record to JSON with hex-encoded field values.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("leaf record serialises to JSON")
+    }
+
+    /// Parse a record produced by [`LeafRecord::to_json`].
+    pub fn from_json(s: &str) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(s)?)
+    }
+
     pub fn recompute_leaf_hash(&self) -> Field {
         match self {
             LeafRecord::Spend {
@@ -195,7 +375,7 @@ impl LeafRecord {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct CandidateWithRecord {
     /// Caller-chosen identifier for the candidate leaf.
     pub leaf_id: Vec<u8>,
@@ -206,15 +386,22 @@ pub struct CandidateWithRecord {
     /// Leaf record reconstructed from the submitted transaction.
     pub record: LeafRecord,
     /// Declared leaf hash (validated before inclusion).
+    #[serde(with = "crate::hex_fmt::field")]
     pub declared_leaf_hash: Field,
 }
 
 /// Validate candidate leaves (hash consistency, membership constraints) and plan a block.
+///
+/// `previously_spent` and `previously_produced` carry the commitments consumed
+/// and created by earlier blocks; they seed the per-block tracking so a note
+/// spent in a prior block cannot be spent again here.
 pub fn validate_and_plan_block<FExists>(
     block_id: u64,
     acceptance_root: Field,
     mut candidates: Vec<CandidateWithRecord>,
     membership_exists: FExists,
+    previously_spent: &HashSet<[u8; 32]>,
+    previously_produced: &HashSet<[u8; 32]>,
 ) -> BindingBlock
 where
     FExists: Fn(Field) -> bool,
@@ -226,9 +413,8 @@ where
             .then_with(|| a.publisher_id.cmp(&b.publisher_id))
     });
 
-    use std::collections::HashSet;
-    let mut produced: HashSet<[u8; 32]> = HashSet::new();
-    let mut consumed: HashSet<[u8; 32]> = HashSet::new();
+    let mut produced: HashSet<[u8; 32]> = previously_produced.clone();
+    let mut consumed: HashSet<[u8; 32]> = previously_spent.clone();
     let mut leaves: Vec<BindingLeaf> = Vec::new();
 
     for cand in candidates.into_iter() {
@@ -254,15 +440,15 @@ where
         });
     }
 
-    plan_block(block_id, acceptance_root, leaves)
+    plan_pair_complete_block(block_id, acceptance_root, leaves)
 }
 
 /// Check whether all inputs of a leaf record are available and unused.
 fn inputs_ok<FExists>(
     record: &LeafRecord,
     membership_exists: &FExists,
-    produced: &std::collections::HashSet<[u8; 32]>,
-    consumed: &std::collections::HashSet<[u8; 32]>,
+    produced: &HashSet<[u8; 32]>,
+    consumed: &HashSet<[u8; 32]>,
 ) -> bool
 where
     FExists: Fn(Field) -> bool,
@@ -289,19 +475,52 @@ pub fn canonical_root_even(hashes: &[Field]) -> Option<Field> {
     }
     let mut level: Vec<Field> = hashes.to_vec();
     while level.len() > 1 {
-        let mut next: Vec<Field> = Vec::with_capacity(level.len() / 2);
-        for pair in level.chunks_exact(2) {
-            let [left, right] = match pair {
-                [l, r] => [*l, *r],
-                _ => return None,
-            };
-            next.push(h2(left, right));
-        }
-        level = next;
+        let pairs: Vec<(Field, Field)> = level
+            .chunks_exact(2)
+            .filter_map(|pair| match pair {
+                [l, r] => Some((*l, *r)),
+                _ => None,
+            })
+            .collect();
+        level = h2_batch(&pairs);
     }
     level.first().copied()
 }
 
+/// Output commitments created by `block`, in block order.
+///
+/// Each included leaf is matched to the record whose recomputed leaf hash
+/// equals it, so `records` may be supplied in any order; leaves without a
+/// matching record contribute nothing. The deferred leaf is not part of the
+/// block and is skipped.
+pub fn apply_block_outputs(block: &BindingBlock, records: &[LeafRecord]) -> Vec<Field> {
+    let by_hash: HashMap<[u8; 32], &LeafRecord> = records
+        .iter()
+        .map(|record| (record.recompute_leaf_hash().to_bytes(), record))
+        .collect();
+    block
+        .iter_leaves()
+        .filter_map(|leaf| by_hash.get(&leaf.leaf_hash.to_bytes()))
+        .flat_map(|record| record.outputs())
+        .collect()
+}
+
+/// Root of `tree` after appending `new_leaves`, leaving `tree` untouched.
+///
+/// The leaves take the next free slots and are hashed up through the tree's
+/// frontier, so the result is the root an [`IncrementalMerkleTree`] reaches
+/// by appending the same leaves. Unfilled slots hold zero leaves; an empty
+/// tree's root is the all-zero subtree root of height `D`. Fails when the
+/// leaves do not fit in the remaining capacity.
+pub fn compute_new_root<const D: usize>(
+    tree: &IncrementalMerkleTree<D>,
+    new_leaves: &[Field],
+) -> anyhow::Result<Field> {
+    let mut next = tree.clone();
+    next.extend(new_leaves)?;
+    Ok(next.root())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
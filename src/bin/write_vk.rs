@@ -1,37 +1,102 @@
 use std::env;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Context;
 
+const USAGE: &str = "usage: write_vk [--verify] <circuit-name> <acir-path> <abi-json> <vk-output>\n       write_vk --all [--verify] <output-dir>";
+
 fn main() -> anyhow::Result<()> {
-    let mut args = env::args().skip(1);
-    let name = args
-        .next()
-        .context("usage: write_vk <circuit-name> <acir-path> <abi-json> <vk-output>")?;
-    let acir_path = PathBuf::from(
-        args.next()
-            .context("usage: write_vk <circuit-name> <acir-path> <abi-json> <vk-output>")?,
-    );
-    let abi_path = PathBuf::from(
-        args.next()
-            .context("usage: write_vk <circuit-name> <acir-path> <abi-json> <vk-output>")?,
-    );
-    let vk_path = PathBuf::from(
-        args.next()
-            .context("usage: write_vk <circuit-name> <acir-path> <abi-json> <vk-output>")?,
-    );
+    let mut all = false;
+    let mut verify = false;
+    let mut positional = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--verify" => verify = true,
+            _ => positional.push(arg),
+        }
+    }
+
+    if all {
+        let [out_dir] = <[String; 1]>::try_from(positional).ok().context(USAGE)?;
+        write_all(Path::new(&out_dir), verify)?;
+    } else {
+        let [name, acir_path, abi_path, vk_path] =
+            <[String; 4]>::try_from(positional).ok().context(USAGE)?;
+        let acir_path = PathBuf::from(acir_path);
+        let abi_path = PathBuf::from(abi_path);
+        let acir = fs::read(&acir_path).with_context(|| format!("reading {acir_path:?}"))?;
+        let abi_json =
+            fs::read_to_string(&abi_path).with_context(|| format!("reading {abi_path:?}"))?;
+        write_one(&name, &acir, &abi_json, Path::new(&vk_path), verify)?;
+    }
+
+    let mut loaded = usernode_circuits::catalog::get_all_with_descriptions();
+    loaded.sort();
+    println!("loaded circuits:");
+    for (name, description) in loaded {
+        match description {
+            Some(description) => println!("  {name}: {description}"),
+            None => println!("  {name}"),
+        }
+    }
+    Ok(())
+}
 
-    let acir = fs::read(&acir_path).with_context(|| format!("reading {acir_path:?}"))?;
-    let abi_json =
-        fs::read_to_string(&abi_path).with_context(|| format!("reading {abi_path:?}"))?;
+/// Regenerate the verifying key of every embedded circuit into `out_dir`.
+///
+/// Failures are reported per circuit; the run only errors after every circuit
+/// has been attempted.
+fn write_all(out_dir: &Path, verify: bool) -> anyhow::Result<()> {
+    fs::create_dir_all(out_dir).with_context(|| format!("creating {out_dir:?}"))?;
+    let embedded = usernode_circuits::artifacts::embedded();
+    let mut written = 0usize;
+    for embed in embedded {
+        let vk_path = out_dir.join(format!("{}.vk", embed.name));
+        println!("{}: {}", embed.name, embed.description);
+        match write_one(embed.name, embed.acir, embed.abi_json, &vk_path, verify) {
+            Ok(()) => written = written.saturating_add(1),
+            Err(err) => eprintln!("error: {}: {err:#}", embed.name),
+        }
+    }
+    println!("wrote {written} verifying keys");
+    anyhow::ensure!(
+        written == embedded.len(),
+        "failed to write {} of {} verifying keys",
+        embedded.len().saturating_sub(written),
+        embedded.len()
+    );
+    Ok(())
+}
 
-    usernode_circuits::prover::init_circuit_from_artifacts(&name, &acir, &[], &abi_json)?;
-    let vk = usernode_circuits::prover::regenerate_vk(&name)?;
-    fs::write(&vk_path, &vk).with_context(|| format!("writing {vk_path:?}"))?;
+fn write_one(
+    name: &str,
+    acir: &[u8],
+    abi_json: &str,
+    vk_path: &Path,
+    verify: bool,
+) -> anyhow::Result<()> {
+    usernode_circuits::prover::init_circuit_from_artifacts(name, acir, &[], abi_json)?;
+    let vk = usernode_circuits::prover::regenerate_vk(name)?;
+    fs::write(vk_path, &vk).with_context(|| format!("writing {vk_path:?}"))?;
     println!(
         "wrote verifying key for {name} ({bytes} bytes) to {vk_path:?}",
         bytes = vk.len()
     );
+
+    if verify {
+        let written = fs::read(vk_path).with_context(|| format!("reading {vk_path:?}"))?;
+        let hash = aztec_barretenberg_rs::mega_vk_hash(&written)
+            .with_context(|| format!("hashing {vk_path:?}"))?;
+        let expected = usernode_circuits::prover::get_circuit(name)
+            .and_then(|entry| entry.vk_hash)
+            .with_context(|| format!("no cached verifying key hash for {name}"))?;
+        anyhow::ensure!(
+            hash == expected,
+            "verifying key written to {vk_path:?} does not match the catalog hash for {name}"
+        );
+        println!("verified verifying key for {name}");
+    }
     Ok(())
 }
@@ -0,0 +1,81 @@
+//! Small binprot building blocks shared by the hand-written transaction codecs.
+//!
+//! Byte strings and fixed arrays are written as length-prefixed byte vectors,
+//! like [`Field`](crate::bn254::Field), and options and variants use a single
+//! tag byte, matching binprot's encoding for types with few constructors.
+//! Counters use binprot's native integer encoding.
+
+use std::io::{Read, Write};
+
+use binprot::{BinProtRead, BinProtWrite};
+
+/// Wrap a decoding failure in binprot's error type.
+pub(crate) fn invalid_data(message: String) -> binprot::Error {
+    binprot::Error::CustomError(Box::new(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message,
+    )))
+}
+
+pub(crate) fn write_bytes<W: Write>(w: &mut W, bytes: &[u8]) -> std::io::Result<()> {
+    bytes.to_vec().binprot_write(w)
+}
+
+pub(crate) fn read_bytes<R: Read + ?Sized>(r: &mut R) -> Result<Vec<u8>, binprot::Error> {
+    Vec::<u8>::binprot_read(r)
+}
+
+/// Read a length-prefixed byte vector that must hold exactly `N` bytes.
+pub(crate) fn read_array<const N: usize, R: Read + ?Sized>(
+    r: &mut R,
+) -> Result<[u8; N], binprot::Error> {
+    let bytes = read_bytes(r)?;
+    let len = bytes.len();
+    bytes
+        .try_into()
+        .map_err(|_| invalid_data(format!("expected {N} bytes, got {len}")))
+}
+
+pub(crate) fn write_u32<W: Write>(w: &mut W, value: u32) -> std::io::Result<()> {
+    i64::from(value).binprot_write(w)
+}
+
+pub(crate) fn read_u32<R: Read + ?Sized>(r: &mut R) -> Result<u32, binprot::Error> {
+    let value = i64::binprot_read(r)?;
+    u32::try_from(value).map_err(|_| invalid_data(format!("{value} does not fit in a u32")))
+}
+
+pub(crate) fn write_tag<W: Write>(w: &mut W, tag: u8) -> std::io::Result<()> {
+    w.write_all(&[tag])
+}
+
+pub(crate) fn read_tag<R: Read + ?Sized>(r: &mut R) -> Result<u8, binprot::Error> {
+    let mut tag = [0u8; 1];
+    r.read_exact(&mut tag)
+        .map_err(|err| binprot::Error::CustomError(Box::new(err)))?;
+    let [tag] = tag;
+    Ok(tag)
+}
+
+pub(crate) fn write_option<T: BinProtWrite, W: Write>(
+    w: &mut W,
+    value: Option<&T>,
+) -> std::io::Result<()> {
+    match value {
+        None => write_tag(w, 0),
+        Some(value) => {
+            write_tag(w, 1)?;
+            value.binprot_write(w)
+        }
+    }
+}
+
+pub(crate) fn read_option<T: BinProtRead, R: Read + ?Sized>(
+    r: &mut R,
+) -> Result<Option<T>, binprot::Error> {
+    match read_tag(r)? {
+        0 => Ok(None),
+        1 => Ok(Some(T::binprot_read(r)?)),
+        tag => Err(invalid_data(format!("invalid option tag {tag}"))),
+    }
+}
@@ -1,6 +1,12 @@
 #![allow(clippy::module_name_repetitions)]
 #![deny(unsafe_op_in_unsafe_fn)]
 
+/// Big-endian encoding of the BN254 scalar field modulus.
+pub const MODULUS_BE: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
 #[derive(Copy, Clone, PartialEq, Eq, Debug, Default, serde::Serialize, serde::Deserialize)]
 pub struct Field([u8; 32]);
 
@@ -19,13 +25,58 @@ impl Field {
         Self::from(1u128)
     }
 
+    /// Build a field element from its **big-endian** 32-byte encoding.
+    ///
+    /// The bytes are stored as given. For canonical encodings (below
+    /// [`MODULUS_BE`]) this equals [`from_be_bytes_reduced`](Self::from_be_bytes_reduced);
+    /// larger values are only reduced once they reach arithmetic, hashing, or
+    /// the prover, so two encodings of the same element would compare unequal.
+    /// Prefer [`from_bytes_checked`](Self::from_bytes_checked) for untrusted input.
     pub const fn from_bytes(bytes: [u8; 32]) -> Self {
         Field(bytes)
     }
 
+    /// Build a field element from big-endian bytes, reducing modulo the field order.
+    pub fn from_be_bytes_reduced(bytes: [u8; 32]) -> Self {
+        crate::field::from_acir_field(crate::field::from_be_bytes(&bytes))
+    }
+
+    /// Build a field element from big-endian bytes, rejecting values at or
+    /// above the modulus instead of reducing them.
+    pub fn from_bytes_checked(bytes: [u8; 32]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            bytes < MODULUS_BE,
+            "field encoding {} is not below the BN254 modulus",
+            crate::hex_fmt::prefixed(&bytes)
+        );
+        Ok(Field(bytes))
+    }
+
+    /// Return the **big-endian** 32-byte encoding.
     pub const fn to_bytes(self) -> [u8; 32] {
         self.0
     }
+
+    /// Build a field element from its **little-endian** 32-byte encoding, as
+    /// produced by some Noir and Ethereum tooling.
+    pub fn from_bytes_le(mut bytes: [u8; 32]) -> Self {
+        bytes.reverse();
+        Self::from_bytes(bytes)
+    }
+
+    /// Return the **little-endian** 32-byte encoding (the reverse of `to_bytes`).
+    pub fn to_bytes_le(self) -> [u8; 32] {
+        let mut bytes = self.to_bytes();
+        bytes.reverse();
+        bytes
+    }
+
+    /// Big-endian encoding as `0x` followed by 64 lower-case hex digits.
+    ///
+    /// This is also the [`Display`](std::fmt::Display) form.
+    pub fn to_hex(&self) -> String {
+        crate::hex_fmt::prefixed(&self.0)
+    }
 }
 
 impl AsRef<[u8; 32]> for Field {
@@ -34,6 +85,25 @@ impl AsRef<[u8; 32]> for Field {
     }
 }
 
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+/// All 256 bits of the big-endian encoding, zero-padded; `{:#b}` adds `0b`.
+impl std::fmt::Binary for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if f.alternate() {
+            f.write_str("0b")?;
+        }
+        for byte in self.0 {
+            write!(f, "{byte:08b}")?;
+        }
+        Ok(())
+    }
+}
+
 impl From<[u8; 32]> for Field {
     fn from(bytes: [u8; 32]) -> Self {
         Field(bytes)
@@ -88,12 +158,31 @@ impl core::ops::SubAssign for Field {
     }
 }
 
+impl core::iter::Sum for Field {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Field::zero(), core::ops::Add::add)
+    }
+}
+
+impl<'a> core::iter::Sum<&'a Field> for Field {
+    fn sum<I: Iterator<Item = &'a Field>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl core::iter::Product for Field {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Field::one(), core::ops::Mul::mul)
+    }
+}
+
 impl PartialOrd for Field {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
         Some(ffi::fr_cmp(self, other))
     }
 }
 
+#[cfg(not(feature = "pure_rust"))]
 mod ffi {
     use super::Field;
 
@@ -184,6 +273,51 @@ mod ffi {
     }
 }
 
+/// Pure-Rust drop-in for the Barretenberg field operations above.
+///
+/// Arithmetic runs on `acir_field::FieldElement`, which already reduces modulo
+/// the BN254 scalar field, so results match the FFI path byte for byte.
+#[cfg(feature = "pure_rust")]
+#[allow(clippy::arithmetic_side_effects)]
+mod ffi {
+    use acir::AcirField;
+    use acir_field::FieldElement;
+
+    use super::Field;
+
+    #[inline]
+    fn to_fe(f: &Field) -> FieldElement {
+        FieldElement::from_be_bytes_reduce(&f.0)
+    }
+
+    #[inline]
+    fn from_fe(fe: FieldElement) -> Field {
+        let mut be = [0u8; 32];
+        be.copy_from_slice(&fe.to_be_bytes());
+        Field(be)
+    }
+
+    #[inline]
+    pub fn fr_add(a: &Field, b: &Field) -> Field {
+        from_fe(to_fe(a) + to_fe(b))
+    }
+
+    #[inline]
+    pub fn fr_sub(a: &Field, b: &Field) -> Field {
+        from_fe(to_fe(a) - to_fe(b))
+    }
+
+    #[inline]
+    pub fn fr_mul(a: &Field, b: &Field) -> Field {
+        from_fe(to_fe(a) * to_fe(b))
+    }
+
+    #[inline]
+    pub fn fr_cmp(a: &Field, b: &Field) -> core::cmp::Ordering {
+        to_fe(a).to_be_bytes().cmp(&to_fe(b).to_be_bytes())
+    }
+}
+
 impl binprot::BinProtWrite for Field {
     fn binprot_write<W: std::io::Write>(&self, w: &mut W) -> std::io::Result<()> {
         let v: Vec<u8> = self.0.to_vec();
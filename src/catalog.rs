@@ -1,6 +1,9 @@
 use std::collections::HashMap;
-use std::sync::{Mutex, OnceLock};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
 
+use acir_field::FieldElement as FE;
 use anyhow::Context;
 
 use crate::artifacts;
@@ -9,22 +12,74 @@ use crate::barretenberg::with_bb_lock;
 #[derive(Clone)]
 pub struct CircuitEntry {
     pub name: String,
+    /// Semver tag of the circuit source; empty for circuits registered at runtime.
+    pub version: String,
     pub acir: Vec<u8>,
+    /// SHA256 of `acir`.
+    pub acir_hash: [u8; 32],
     pub vk: Vec<u8>,
     pub abi: Abi,
     pub key_id: [u8; 32],
     pub vk_hash: Option<[u8; 32]>,
+    /// Human-readable summary for tooling; `None` for circuits registered at runtime.
+    pub description: Option<String>,
 }
 
-static CACHE: OnceLock<Mutex<HashMap<String, CircuitEntry>>> = OnceLock::new();
-static VK_CACHE: OnceLock<Mutex<HashMap<[u8; 32], VkEntry>>> = OnceLock::new();
+impl CircuitEntry {
+    /// Field elements the circuit expects across its private parameters.
+    pub fn private_input_field_count(&self) -> usize {
+        self.abi.total_private_field_count()
+    }
+
+    /// Field elements the circuit expects across every parameter.
+    pub fn total_parameter_field_count(&self) -> usize {
+        self.abi.total_field_count()
+    }
+
+    /// Whether `path` (e.g. `input.transfer.token`) names a parameter or one
+    /// of its nested fields.
+    pub fn has_parameter(&self, path: &str) -> bool {
+        self.abi.find_parameter(path).is_some()
+    }
 
-fn cache() -> &'static Mutex<HashMap<String, CircuitEntry>> {
-    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+    /// Whether Barretenberg has compiled this circuit and assigned a key id.
+    ///
+    /// Entries registered by [`register_embedded_lazy`] start uncompiled and
+    /// are compiled by the first [`get`].
+    pub fn is_compiled(&self) -> bool {
+        self.key_id != [0u8; 32]
+    }
 }
 
-fn vk_cache() -> &'static Mutex<HashMap<[u8; 32], VkEntry>> {
-    VK_CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+// Lookups vastly outnumber registrations, so readers share the maps and only
+// registration, key updates, and `clear` take the write side.
+static CACHE: OnceLock<RwLock<HashMap<String, CircuitEntry>>> = OnceLock::new();
+static VK_CACHE: OnceLock<RwLock<HashMap<[u8; 32], VkEntry>>> = OnceLock::new();
+/// Per-circuit locks so only the first caller compiles a lazy entry.
+static COMPILE_LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+fn cache() -> &'static RwLock<HashMap<String, CircuitEntry>> {
+    CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn vk_cache() -> &'static RwLock<HashMap<[u8; 32], VkEntry>> {
+    VK_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+fn compile_locks() -> &'static Mutex<HashMap<String, Arc<Mutex<()>>>> {
+    COMPILE_LOCKS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Compile lock for `name`, or `None` if no such circuit is registered.
+///
+/// Locks are only created for registered names and are dropped again by
+/// [`remove`] and [`clear`], so arbitrary lookups cannot grow the map.
+fn compile_lock(name: &str) -> Option<Arc<Mutex<()>>> {
+    let mut locks = compile_locks().lock().unwrap();
+    if !cache().read().unwrap().contains_key(name) {
+        return None;
+    }
+    Some(locks.entry(name.to_string()).or_default().clone())
 }
 
 #[derive(Clone)]
@@ -34,21 +89,21 @@ pub struct VkEntry {
 }
 
 pub fn upsert_vk_entry(id: [u8; 32], bytes: Vec<u8>, hash: Option<[u8; 32]>) {
-    let mut guard = vk_cache().lock().unwrap();
+    let mut guard = vk_cache().write().unwrap();
     guard.insert(id, VkEntry { bytes, hash });
 }
 
 pub fn remove_vk_entry(id: &[u8; 32]) {
-    let mut guard = vk_cache().lock().unwrap();
+    let mut guard = vk_cache().write().unwrap();
     guard.remove(id);
 }
 
 pub fn get_vk_entry_by_id(id: &[u8; 32]) -> Option<VkEntry> {
-    vk_cache().lock().unwrap().get(id).cloned()
+    vk_cache().read().unwrap().get(id).cloned()
 }
 
 pub fn upsert_vk_hash(id: [u8; 32], hash: [u8; 32]) {
-    let mut guard = vk_cache().lock().unwrap();
+    let mut guard = vk_cache().write().unwrap();
     guard
         .entry(id)
         .and_modify(|entry| entry.hash = Some(hash))
@@ -58,12 +113,108 @@ pub fn upsert_vk_hash(id: [u8; 32], hash: [u8; 32]) {
         });
 }
 
+/// UTXO tree depth used when `UTXO_TREE_DEPTH` is unset.
+pub const DEFAULT_UTXO_TREE_DEPTH: u32 = 32;
+
+static UTXO_TREE_DEPTH: OnceLock<u32> = OnceLock::new();
+
+/// Parse `UTXO_TREE_DEPTH`, falling back to [`DEFAULT_UTXO_TREE_DEPTH`].
+fn utxo_tree_depth_from_env() -> anyhow::Result<u32> {
+    let Ok(raw) = std::env::var("UTXO_TREE_DEPTH") else {
+        return Ok(DEFAULT_UTXO_TREE_DEPTH);
+    };
+    let depth: u32 = raw
+        .trim()
+        .parse()
+        .with_context(|| format!("UTXO_TREE_DEPTH={raw} is not a number"))?;
+    anyhow::ensure!(
+        (1..=crate::merkle::MAX_TREE_DEPTH as u32).contains(&depth),
+        "UTXO_TREE_DEPTH={depth} must be between 1 and {}",
+        crate::merkle::MAX_TREE_DEPTH
+    );
+    Ok(depth)
+}
+
+/// Depth of the UTXO commitment tree, read once from `UTXO_TREE_DEPTH`.
+///
+/// [`init_embedded`] loads and validates the setting; before that, an invalid
+/// value falls back to [`DEFAULT_UTXO_TREE_DEPTH`].
+pub fn utxo_tree_depth() -> u32 {
+    *UTXO_TREE_DEPTH.get_or_init(|| utxo_tree_depth_from_env().unwrap_or(DEFAULT_UTXO_TREE_DEPTH))
+}
+
 pub fn all_loaded() -> Vec<String> {
-    cache().lock().unwrap().keys().cloned().collect()
+    cache().read().unwrap().keys().cloned().collect()
+}
+
+/// Loaded circuit names paired with their descriptions, in no particular order.
+pub fn get_all_with_descriptions() -> Vec<(String, Option<String>)> {
+    cache()
+        .read()
+        .unwrap()
+        .values()
+        .map(|entry| (entry.name.clone(), entry.description.clone()))
+        .collect()
 }
 
+/// Look up `name`, compiling it first if it was registered lazily.
+///
+/// Only the first caller for a given circuit pays for `compile_mega`; the
+/// rest wait on a per-circuit lock and get the compiled entry. Must not be
+/// called while holding the Barretenberg lock. If compilation fails this
+/// returns `None`, never the uncompiled entry; [`get_compiled`] surfaces the
+/// error instead.
 pub fn get(name: &str) -> Option<CircuitEntry> {
-    cache().lock().unwrap().get(name).cloned()
+    get_compiled(name).ok().flatten()
+}
+
+/// [`get`], reporting a failed lazy compilation as an error.
+///
+/// Entries that are already compiled are returned straight from the cache;
+/// only the uncompiled path takes the compile lock.
+pub fn get_compiled(name: &str) -> anyhow::Result<Option<CircuitEntry>> {
+    match peek(name) {
+        None => return Ok(None),
+        Some(entry) if entry.is_compiled() => return Ok(Some(entry)),
+        Some(_) => {}
+    }
+    let Some(lock) = compile_lock(name) else {
+        return Ok(None);
+    };
+    let _compiling = lock.lock().unwrap();
+    let Some(entry) = peek(name) else {
+        return Ok(None);
+    };
+    if entry.is_compiled() {
+        return Ok(Some(entry));
+    }
+    let key_id = with_bb_lock(|| aztec_barretenberg_rs::compile_mega(&entry.acir))
+        .with_context(|| format!("compile_mega for {name}"))?;
+    let vk_hash = match entry.vk_hash {
+        Some(hash) => Some(hash),
+        None if entry.vk.is_empty() => None,
+        None => Some(
+            aztec_barretenberg_rs::mega_vk_hash(&entry.vk)
+                .with_context(|| format!("vk hash for {name}"))?,
+        ),
+    };
+    let mut cache = cache().write().unwrap();
+    let Some(cached) = cache.get_mut(name) else {
+        return Ok(None);
+    };
+    if !cached.is_compiled() {
+        cached.key_id = key_id;
+        cached.vk_hash = vk_hash;
+        if !cached.vk.is_empty() {
+            upsert_vk_entry(key_id, cached.vk.clone(), vk_hash);
+        }
+    }
+    Ok(Some(cached.clone()))
+}
+
+/// Look up `name` without triggering lazy compilation.
+pub fn peek(name: &str) -> Option<CircuitEntry> {
+    cache().read().unwrap().get(name).cloned()
 }
 
 pub fn insert(entry: CircuitEntry) {
@@ -72,11 +223,21 @@ pub fn insert(entry: CircuitEntry) {
     } else {
         upsert_vk_entry(entry.key_id, entry.vk.clone(), entry.vk_hash);
     }
-    cache().lock().unwrap().insert(entry.name.clone(), entry);
+    cache().write().unwrap().insert(entry.name.clone(), entry);
+}
+
+/// Unregister `name`, dropping its cached verifying key as well.
+pub fn remove(name: &str) -> Option<CircuitEntry> {
+    let removed = cache().write().unwrap().remove(name);
+    if let Some(entry) = &removed {
+        remove_vk_entry(&entry.key_id);
+    }
+    compile_locks().lock().unwrap().remove(name);
+    removed
 }
 
 pub fn update_vk(name: &str, vk: &[u8], vk_hash: Option<[u8; 32]>, key_id: Option<[u8; 32]>) {
-    if let Some(entry) = cache().lock().unwrap().get_mut(name) {
+    if let Some(entry) = cache().write().unwrap().get_mut(name) {
         if entry.vk.is_empty() || entry.vk != vk {
             entry.vk = vk.to_vec();
         }
@@ -95,26 +256,80 @@ pub fn update_vk(name: &str, vk: &[u8], vk_hash: Option<[u8; 32]>, key_id: Optio
 }
 
 pub fn clear() {
-    cache().lock().unwrap().clear();
-    vk_cache().lock().unwrap().clear();
+    cache().write().unwrap().clear();
+    vk_cache().write().unwrap().clear();
+    compile_locks().lock().unwrap().clear();
 }
 
-pub fn hydrate(entries: &[CircuitEntry]) {
-    let mut cache = cache().lock().unwrap();
+/// Register every entry under a single acquisition of the catalog lock, so
+/// concurrent readers see either none or all of them.
+pub fn insert_many(entries: Vec<CircuitEntry>) {
+    let mut cache = cache().write().unwrap();
     for entry in entries {
         if entry.vk.is_empty() {
             remove_vk_entry(&entry.key_id);
         } else {
             upsert_vk_entry(entry.key_id, entry.vk.clone(), entry.vk_hash);
         }
-        cache.insert(entry.name.clone(), entry.clone());
+        cache.insert(entry.name.clone(), entry);
     }
 }
 
+pub fn hydrate(entries: &[CircuitEntry]) {
+    insert_many(entries.to_vec());
+}
+
+fn load_utxo_tree_depth() -> anyhow::Result<()> {
+    if UTXO_TREE_DEPTH.get().is_none() {
+        let depth = utxo_tree_depth_from_env()?;
+        UTXO_TREE_DEPTH.get_or_init(|| depth);
+    }
+    Ok(())
+}
+
+/// Register the embedded circuits without compiling them.
+///
+/// Each entry is compiled by the first [`get`] for its name, so binaries that
+/// only use some circuits skip `compile_mega` for the rest. Circuits that are
+/// already registered are left alone. Returns the names registered.
+pub fn register_embedded_lazy() -> anyhow::Result<Vec<String>> {
+    load_utxo_tree_depth()?;
+    let mut entries = Vec::new();
+    for embed in artifacts::embedded() {
+        #[cfg(not(feature = "skip_checksum"))]
+        embed.verify_checksum()?;
+        let abi: Abi = serde_json::from_str(embed.abi_json)
+            .with_context(|| format!("parsing ABI for {}", embed.name))?;
+        entries.push(CircuitEntry {
+            name: embed.name.to_string(),
+            version: embed.version.to_string(),
+            acir: embed.acir.to_vec(),
+            acir_hash: embed.acir_hash,
+            vk: embed.vk.to_vec(),
+            abi,
+            key_id: [0u8; 32],
+            vk_hash: None,
+            description: Some(embed.description.to_string()),
+        });
+    }
+    let mut cache = cache().write().unwrap();
+    let mut names = Vec::new();
+    for entry in entries {
+        if !cache.contains_key(&entry.name) {
+            names.push(entry.name.clone());
+            cache.insert(entry.name.clone(), entry);
+        }
+    }
+    Ok(names)
+}
+
 pub fn init_embedded() -> anyhow::Result<Vec<CircuitEntry>> {
+    load_utxo_tree_depth()?;
     let mut entries = Vec::new();
-    let mut cache_guard = cache().lock().unwrap();
+    let mut cache_guard = cache().write().unwrap();
     for embed in artifacts::embedded() {
+        #[cfg(not(feature = "skip_checksum"))]
+        embed.verify_checksum()?;
         let abi: Abi = serde_json::from_str(embed.abi_json)
             .with_context(|| format!("parsing ABI for {}", embed.name))?;
         let key_id = with_bb_lock(|| aztec_barretenberg_rs::compile_mega(embed.acir))
@@ -130,11 +345,14 @@ pub fn init_embedded() -> anyhow::Result<Vec<CircuitEntry>> {
         };
         let entry = CircuitEntry {
             name: embed.name.to_string(),
+            version: embed.version.to_string(),
             acir: embed.acir.to_vec(),
+            acir_hash: embed.acir_hash,
             vk: vk_vec,
             abi,
             key_id,
             vk_hash,
+            description: Some(embed.description.to_string()),
         };
         if entry.vk.is_empty() {
             remove_vk_entry(&entry.key_id);
@@ -147,14 +365,236 @@ pub fn init_embedded() -> anyhow::Result<Vec<CircuitEntry>> {
     Ok(entries)
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+/// Register every `<stem>.acir` in `dir` that has matching `<stem>.vk` and
+/// `<stem>.abi.json` siblings, returning the registered circuit names.
+///
+/// Each triple goes through `prover::init_circuit_from_artifacts`, so an
+/// empty `.vk` file is regenerated from the ACIR. Files without a complete
+/// triple are skipped.
+pub fn import_from_directory(dir: &Path) -> anyhow::Result<Vec<String>> {
+    let mut acir_paths = Vec::new();
+    for dir_entry in fs::read_dir(dir).with_context(|| format!("reading {dir:?}"))? {
+        let path = dir_entry
+            .with_context(|| format!("reading {dir:?}"))?
+            .path();
+        if path.extension().is_some_and(|ext| ext == "acir") {
+            acir_paths.push(path);
+        }
+    }
+    acir_paths.sort();
+
+    let mut names = Vec::new();
+    for acir_path in acir_paths {
+        let Some(name) = acir_path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+        let vk_path = dir.join(format!("{name}.vk"));
+        let abi_path = dir.join(format!("{name}.abi.json"));
+        if !vk_path.is_file() || !abi_path.is_file() {
+            continue;
+        }
+        let acir = fs::read(&acir_path).with_context(|| format!("reading {acir_path:?}"))?;
+        let vk = fs::read(&vk_path).with_context(|| format!("reading {vk_path:?}"))?;
+        let abi_json =
+            fs::read_to_string(&abi_path).with_context(|| format!("reading {abi_path:?}"))?;
+        crate::prover::init_circuit_from_artifacts(name, &acir, &vk, &abi_json)?;
+        names.push(name.to_string());
+    }
+    Ok(names)
+}
+
+/// Write every loaded circuit to `dir` as `<name>.acir`, `<name>.vk`, and
+/// `<name>.abi.json`, returning the exported circuit names.
+///
+/// This is the inverse of [`import_from_directory`]. The ABI is re-serialised
+/// from the parsed [`Abi`], so fields the catalog does not model (such as
+/// Noir's `error_types`) are not preserved. Entries are written as stored;
+/// lazily registered circuits are not compiled first.
+pub fn export_to_directory(dir: &Path) -> anyhow::Result<Vec<String>> {
+    fs::create_dir_all(dir).with_context(|| format!("creating {dir:?}"))?;
+    let mut names = all_loaded();
+    names.sort();
+
+    let mut exported = Vec::with_capacity(names.len());
+    for name in names {
+        let Some(entry) = peek(&name) else {
+            continue;
+        };
+        let acir_path = dir.join(format!("{name}.acir"));
+        let vk_path = dir.join(format!("{name}.vk"));
+        let abi_path = dir.join(format!("{name}.abi.json"));
+        let abi_json = serde_json::to_string_pretty(&entry.abi)
+            .with_context(|| format!("serialising ABI for {name}"))?;
+        fs::write(&acir_path, &entry.acir).with_context(|| format!("writing {acir_path:?}"))?;
+        fs::write(&vk_path, &entry.vk).with_context(|| format!("writing {vk_path:?}"))?;
+        fs::write(&abi_path, abi_json).with_context(|| format!("writing {abi_path:?}"))?;
+        exported.push(name);
+    }
+    Ok(exported)
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Abi {
     pub parameters: Vec<AbiParam>,
     #[allow(dead_code)]
     pub return_type: Option<AbiReturn>,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+impl Abi {
+    /// Number of top-level parameters with the given visibility (`"private"`/`"public"`).
+    pub fn parameter_count(&self, visibility: &str) -> usize {
+        self.parameters
+            .iter()
+            .filter(|p| p.visibility == visibility)
+            .count()
+    }
+
+    /// Return the top-level parameter that contains `path`.
+    ///
+    /// `path` may name the parameter itself (`"input"`) or a nested field
+    /// (`"input.transfer.token"`); use [`Abi::find_type`] for the nested type.
+    pub fn find_parameter(&self, path: &str) -> Option<&AbiParam> {
+        self.parameters
+            .iter()
+            .find(|p| resolve_path(&p.name, &p.abi_type, path).is_some())
+    }
+
+    /// Resolve the ABI type at a dot-separated `path` such as `input.transfer.token`.
+    pub fn find_type(&self, path: &str) -> Option<&AbiType> {
+        self.parameters
+            .iter()
+            .find_map(|p| resolve_path(&p.name, &p.abi_type, path))
+    }
+
+    /// Total number of field elements across all private parameters.
+    pub fn total_private_field_count(&self) -> usize {
+        self.parameters
+            .iter()
+            .filter(|p| p.visibility == "private")
+            .map(|p| p.abi_type.leaf_count())
+            .sum()
+    }
+
+    /// Total number of field elements across all parameters, public included.
+    pub fn total_field_count(&self) -> usize {
+        self.parameters
+            .iter()
+            .map(|p| p.abi_type.leaf_count())
+            .sum()
+    }
+
+    /// Flattened leaf paths for every parameter, in ABI order.
+    ///
+    /// The paths match the keys `prover::prove_with_abi` expects: struct fields
+    /// and tuple elements are joined with `.` (tuples by position), arrays of
+    /// scalars are a single leaf, and arrays of structs, tuples, or arrays get
+    /// an `[i]` suffix per element.
+    pub fn param_paths(&self) -> Vec<String> {
+        let mut out = Vec::new();
+        for p in &self.parameters {
+            collect_paths(&p.name, &p.abi_type, &mut out);
+        }
+        out
+    }
+
+    /// Check that `inputs` covers every private parameter before proving.
+    ///
+    /// Walks the private parameters the same way `prover::encode_abi_inputs`
+    /// does, but only checks that each leaf key is present with the expected
+    /// number of elements. On failure returns every missing or wrong-length
+    /// key, in ABI order.
+    pub fn validate_inputs(&self, inputs: &HashMap<String, Vec<FE>>) -> Result<(), Vec<String>> {
+        let mut bad = Vec::new();
+        for p in self.parameters.iter().filter(|p| p.visibility == "private") {
+            check_leaves(&p.name, &p.abi_type, inputs, &mut bad);
+        }
+        if bad.is_empty() { Ok(()) } else { Err(bad) }
+    }
+}
+
+fn resolve_path<'a>(name: &str, abi_type: &'a AbiType, path: &str) -> Option<&'a AbiType> {
+    let rest = path.strip_prefix(name)?;
+    if rest.is_empty() {
+        return Some(abi_type);
+    }
+    let rest = rest.strip_prefix('.')?;
+    match abi_type {
+        AbiType::Struct { fields } => fields
+            .iter()
+            .find_map(|f| resolve_path(&f.name, &f.abi_type, rest)),
+        AbiType::Tuple { elements } => elements
+            .iter()
+            .enumerate()
+            .find_map(|(i, elem)| resolve_path(&i.to_string(), elem, rest)),
+        _ => None,
+    }
+}
+
+fn collect_paths(name: &str, abi_type: &AbiType, out: &mut Vec<String>) {
+    match abi_type {
+        AbiType::Struct { fields } => {
+            for f in fields {
+                collect_paths(&format!("{name}.{}", f.name), &f.abi_type, out);
+            }
+        }
+        AbiType::Tuple { elements } => {
+            for (i, elem) in elements.iter().enumerate() {
+                collect_paths(&format!("{name}.{i}"), elem, out);
+            }
+        }
+        AbiType::Array { length, elem }
+            if matches!(
+                **elem,
+                AbiType::Array { .. } | AbiType::Struct { .. } | AbiType::Tuple { .. }
+            ) =>
+        {
+            for i in 0..*length {
+                collect_paths(&format!("{name}[{i}]"), elem, out);
+            }
+        }
+        _ => out.push(name.to_string()),
+    }
+}
+
+fn check_leaves(
+    name: &str,
+    abi_type: &AbiType,
+    inputs: &HashMap<String, Vec<FE>>,
+    bad: &mut Vec<String>,
+) {
+    let expected = match abi_type {
+        AbiType::Struct { fields } => {
+            for f in fields {
+                check_leaves(&format!("{name}.{}", f.name), &f.abi_type, inputs, bad);
+            }
+            return;
+        }
+        AbiType::Tuple { elements } => {
+            for (i, elem) in elements.iter().enumerate() {
+                check_leaves(&format!("{name}.{i}"), elem, inputs, bad);
+            }
+            return;
+        }
+        AbiType::Array { length, elem }
+            if matches!(
+                **elem,
+                AbiType::Array { .. } | AbiType::Struct { .. } | AbiType::Tuple { .. }
+            ) =>
+        {
+            for i in 0..*length {
+                check_leaves(&format!("{name}[{i}]"), elem, inputs, bad);
+            }
+            return;
+        }
+        AbiType::Array { length, .. } => *length,
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => 1,
+    };
+    if inputs.get(name).map(Vec::len) != Some(expected) {
+        bad.push(name.to_string());
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct AbiParam {
     pub name: String,
     #[serde(rename = "type")]
@@ -162,20 +602,20 @@ pub struct AbiParam {
     pub visibility: String,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct AbiReturn {
     pub abi_type: AbiType,
     pub visibility: String,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct AbiStructField {
     pub name: String,
     #[serde(rename = "type")]
     pub abi_type: AbiType,
 }
 
-#[derive(Clone, Debug, serde::Deserialize)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "kind", rename_all = "lowercase")]
 pub enum AbiType {
     Field,
@@ -192,4 +632,57 @@ pub enum AbiType {
     Struct {
         fields: Vec<AbiStructField>,
     },
+    /// Noir tuple; element `i` is addressed like a struct field named `"i"`.
+    Tuple {
+        #[serde(rename = "fields")]
+        elements: Vec<AbiType>,
+    },
+}
+
+impl AbiType {
+    /// Number of scalar field elements this type expands to when encoded.
+    pub fn leaf_count(&self) -> usize {
+        match self {
+            AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => 1,
+            AbiType::Array { length, elem } => length.saturating_mul(elem.leaf_count()),
+            AbiType::Struct { fields } => fields.iter().map(|f| f.abi_type.leaf_count()).sum(),
+            AbiType::Tuple { elements } => elements.iter().map(AbiType::leaf_count).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lock_count() -> usize {
+        compile_locks().lock().unwrap().len()
+    }
+
+    #[test]
+    fn compile_locks_track_registered_names_only() {
+        assert!(compile_lock("never_registered").is_none());
+        assert!(get("never_registered").is_none());
+        assert_eq!(lock_count(), 0);
+
+        insert(CircuitEntry {
+            name: "lock_probe".to_string(),
+            version: String::new(),
+            acir: Vec::new(),
+            acir_hash: [0u8; 32],
+            vk: Vec::new(),
+            abi: Abi {
+                parameters: Vec::new(),
+                return_type: None,
+            },
+            key_id: [0u8; 32],
+            vk_hash: None,
+            description: None,
+        });
+        assert!(compile_lock("lock_probe").is_some());
+        assert_eq!(lock_count(), 1);
+
+        assert!(remove("lock_probe").is_some());
+        assert_eq!(lock_count(), 0);
+    }
 }
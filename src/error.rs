@@ -0,0 +1,46 @@
+//! Typed errors for failure modes callers may want to match on.
+//!
+//! Most of the crate reports failures through `anyhow::Error`; the variants
+//! here are wrapped the same way, so callers recover them with
+//! `err.downcast_ref::<CircuitError>()`.
+
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CircuitError {
+    /// The `ensure_unique` check rejected every salt within the attempt budget.
+    UniquenessRetryExhausted,
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UniquenessRetryExhausted => {
+                f.write_str("output commitment uniqueness retries exhausted")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+/// Inputs rejected before any witness is built or proof attempted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// Two non-empty asset slots hold the same token, which the circuit rejects.
+    DuplicateToken { slot_a: usize, slot_b: usize },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateToken { slot_a, slot_b } => {
+                write!(f, "asset slots {slot_a} and {slot_b} hold the same token")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
@@ -0,0 +1,101 @@
+//! C ABI exports for non-Rust services (requires the `ffi` feature).
+//!
+//! Every function returns a `c_int` status instead of unwinding: panics are
+//! caught at the boundary and reported as `-1`. The matching header lives in
+//! `include/usernode_circuits.h` and is regenerated with `cbindgen`.
+
+use std::ffi::{CStr, c_char, c_int};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::Path;
+
+use crate::bn254::Field;
+use crate::poseidon2::hash_fields;
+use crate::prover;
+
+/// Run `f`, mapping errors and panics to `-1`.
+fn guarded(f: impl FnOnce() -> anyhow::Result<c_int>) -> c_int {
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(code)) => code,
+        Ok(Err(_)) | Err(_) => -1,
+    }
+}
+
+/// Configure the CRS directory (when non-null) and load the embedded circuits.
+///
+/// Returns `0` on success and `-1` on error.
+///
+/// # Safety
+///
+/// `crs_dir` must be null or point to a NUL-terminated UTF-8 string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn usernode_init(crs_dir: *const c_char) -> c_int {
+    guarded(|| {
+        if !crs_dir.is_null() {
+            // SAFETY: the caller guarantees a valid NUL-terminated string.
+            let dir = unsafe { CStr::from_ptr(crs_dir) }.to_str()?;
+            prover::set_crs_directory(Path::new(dir))?;
+        }
+        prover::init_default_circuits()?;
+        Ok(0)
+    })
+}
+
+/// Verify `proof` against the registered circuit `circuit_name`.
+///
+/// Returns `1` for a valid proof, `0` for an invalid one, and `-1` on error
+/// (unknown circuit, bad arguments, or a Barretenberg failure).
+///
+/// # Safety
+///
+/// `circuit_name` must point to a NUL-terminated UTF-8 string and `proof` must
+/// be valid for reads of `proof_len` bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn usernode_verify(
+    circuit_name: *const c_char,
+    proof: *const u8,
+    proof_len: usize,
+) -> c_int {
+    guarded(|| {
+        anyhow::ensure!(!circuit_name.is_null() && !proof.is_null(), "null argument");
+        // SAFETY: the caller guarantees a valid NUL-terminated string.
+        let name = unsafe { CStr::from_ptr(circuit_name) }.to_str()?;
+        // SAFETY: the caller guarantees `proof` covers `proof_len` bytes.
+        let proof = unsafe { std::slice::from_raw_parts(proof, proof_len) };
+        Ok(c_int::from(prover::verify(name, proof)?))
+    })
+}
+
+/// Poseidon2-hash `inputs_len / 32` big-endian field elements into `out`.
+///
+/// Returns `0` on success and `-1` if `inputs_len` is not a multiple of 32 or
+/// a pointer is null.
+///
+/// # Safety
+///
+/// `inputs` must be valid for reads of `inputs_len` bytes and `out` must be
+/// valid for writes of 32 bytes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn usernode_hash_fields(
+    inputs: *const u8,
+    inputs_len: usize,
+    out: *mut u8,
+) -> c_int {
+    guarded(|| {
+        anyhow::ensure!(!inputs.is_null() && !out.is_null(), "null argument");
+        anyhow::ensure!(inputs_len % 32 == 0, "input length not a multiple of 32");
+        // SAFETY: the caller guarantees `inputs` covers `inputs_len` bytes.
+        let bytes = unsafe { std::slice::from_raw_parts(inputs, inputs_len) };
+        let fields: Vec<Field> = bytes
+            .chunks_exact(32)
+            .map(|chunk| {
+                let mut be = [0u8; 32];
+                be.copy_from_slice(chunk);
+                Field::from_bytes(be)
+            })
+            .collect();
+        let digest = hash_fields(&fields).to_bytes();
+        // SAFETY: the caller guarantees `out` has room for 32 bytes.
+        unsafe { std::ptr::copy_nonoverlapping(digest.as_ptr(), out, digest.len()) };
+        Ok(0)
+    })
+}
@@ -18,3 +18,23 @@ pub fn to_be_bytes(fe: CircuitFieldElement) -> [u8; 32] {
         .try_into()
         .expect("acir field encodes to 32 bytes")
 }
+
+/// Convert a BN254 field element into the ACIR representation.
+pub fn to_acir_field(f: Field) -> CircuitFieldElement {
+    from_bn254(&f)
+}
+
+/// Convert an ACIR field element back into the BN254 representation.
+pub fn from_acir_field(fe: CircuitFieldElement) -> Field {
+    Field::from_bytes(to_be_bytes(fe))
+}
+
+/// Batch form of [`from_acir_field`].
+pub fn acir_fields_to_bn254(v: &[CircuitFieldElement]) -> Vec<Field> {
+    v.iter().copied().map(from_acir_field).collect()
+}
+
+/// Batch form of [`to_acir_field`].
+pub fn bn254_fields_to_acir(v: &[Field]) -> Vec<CircuitFieldElement> {
+    v.iter().copied().map(to_acir_field).collect()
+}
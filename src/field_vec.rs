@@ -0,0 +1,40 @@
+//! Small linear-algebra helpers over vectors of BN254 field elements.
+//!
+//! All arithmetic goes through the Barretenberg-backed `Field` operators, so
+//! results are reduced modulo the BN254 scalar field exactly as the circuits
+//! compute them.
+
+use crate::bn254::Field;
+use crate::poseidon2::hash_fields;
+
+/// Sum all elements of `v` (zero for an empty slice).
+pub fn field_sum(v: &[Field]) -> Field {
+    v.iter().sum()
+}
+
+/// Inner product of `a` and `b`; errors when the lengths differ.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn field_dot(a: &[Field], b: &[Field]) -> anyhow::Result<Field> {
+    anyhow::ensure!(
+        a.len() == b.len(),
+        "field_dot length mismatch: {} vs {}",
+        a.len(),
+        b.len()
+    );
+    let mut acc = Field::zero();
+    for (&x, &y) in a.iter().zip(b.iter()) {
+        acc += x * y;
+    }
+    Ok(acc)
+}
+
+/// Multiply every element of `v` by `scalar`.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn field_scale(v: &[Field], scalar: Field) -> Vec<Field> {
+    v.iter().map(|&x| x * scalar).collect()
+}
+
+/// Poseidon2 hash of `v`; alias for [`hash_fields`].
+pub fn hash_vec(v: &[Field]) -> Field {
+    hash_fields(v)
+}
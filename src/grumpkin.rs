@@ -0,0 +1,147 @@
+//! Grumpkin scalar multiplication for note key agreement.
+//!
+//! Grumpkin is `y^2 = x^3 - 17` over the BN254 scalar field, so points are
+//! built from [`Field`] directly. Points are added with the complete
+//! projective formulas of Renes, Costello and Batina (2015, algorithm 7 for
+//! `a = 0`), which have no special cases for doubling or the identity, and
+//! scalars are applied with a Montgomery ladder whose swaps are constant-time.
+//! The group has prime order, so any point on the curve other than the
+//! identity generates it.
+
+use subtle::{Choice, ConditionallySelectable};
+
+use crate::bn254::Field;
+
+/// Affine y-coordinate of the generator `(1, y)`.
+const GENERATOR_Y: [u8; 32] = [
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0xcf, 0x13, 0x5e, 0x75, 0x06, 0xa4, 0x5d, 0x63,
+    0x2d, 0x27, 0x0d, 0x45, 0xf1, 0x18, 0x12, 0x94, 0x83, 0x3f, 0xc4, 0x8d, 0x82, 0x3f, 0x27, 0x2c,
+];
+
+/// BN254 scalar modulus minus two, the Fermat inversion exponent.
+const INVERSE_EXPONENT: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff, 0xff, 0xff,
+];
+
+/// Curve constant `b = -17`.
+const B: u128 = 17;
+
+/// Projective point `(X : Y : Z)` with affine coordinates `(X/Z, Y/Z)`.
+#[derive(Clone, Copy)]
+struct Projective {
+    x: Field,
+    y: Field,
+    z: Field,
+}
+
+impl Projective {
+    fn identity() -> Self {
+        Self {
+            x: Field::zero(),
+            y: Field::one(),
+            z: Field::zero(),
+        }
+    }
+
+    fn from_affine(x: Field, y: Field) -> Self {
+        Self {
+            x,
+            y,
+            z: Field::one(),
+        }
+    }
+
+    // Complete addition; also used for doubling.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn add(&self, other: &Self) -> Self {
+        let b3 = Field::zero() - Field::from(3 * B);
+        let xx = self.x * other.x;
+        let yy = self.y * other.y;
+        let zz = self.z * other.z;
+        let xy = (self.x + self.y) * (other.x + other.y) - xx - yy;
+        let yz = (self.y + self.z) * (other.y + other.z) - yy - zz;
+        let xz = (self.x + self.z) * (other.x + other.z) - xx - zz;
+        let bzz3 = b3 * zz;
+        let yy_minus = yy - bzz3;
+        let yy_plus = yy + bzz3;
+        let byz3 = b3 * yz;
+        let xx3 = xx + xx + xx;
+        let bxx9 = b3 * xx3;
+        Self {
+            x: xy * yy_minus - byz3 * xz,
+            y: yy_plus * yy_minus + bxx9 * xz,
+            z: yz * yy_plus + xx3 * xy,
+        }
+    }
+
+    fn conditional_swap(a: &mut Self, b: &mut Self, swap: Choice) {
+        swap_field(&mut a.x, &mut b.x, swap);
+        swap_field(&mut a.y, &mut b.y, swap);
+        swap_field(&mut a.z, &mut b.z, swap);
+    }
+
+    /// Affine coordinates, or `None` for the identity.
+    #[allow(clippy::arithmetic_side_effects)]
+    fn to_affine(self) -> Option<(Field, Field)> {
+        if self.z == Field::zero() {
+            return None;
+        }
+        let z_inv = pow(self.z, &INVERSE_EXPONENT);
+        Some((self.x * z_inv, self.y * z_inv))
+    }
+}
+
+fn swap_field(a: &mut Field, b: &mut Field, swap: Choice) {
+    let mut left = a.to_bytes();
+    let mut right = b.to_bytes();
+    for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+        u8::conditional_swap(l, r, swap);
+    }
+    *a = Field::from_bytes(left);
+    *b = Field::from_bytes(right);
+}
+
+/// `base^exponent` for a public big-endian exponent.
+#[allow(clippy::arithmetic_side_effects)]
+fn pow(base: Field, exponent: &[u8; 32]) -> Field {
+    let mut acc = Field::one();
+    for byte in exponent {
+        for shift in (0..8).rev() {
+            acc = acc * acc;
+            if (byte >> shift) & 1 == 1 {
+                acc = acc * base;
+            }
+        }
+    }
+    acc
+}
+
+/// Whether `(x, y)` satisfies the curve equation.
+#[allow(clippy::arithmetic_side_effects)]
+pub(crate) fn is_on_curve(x: Field, y: Field) -> bool {
+    y * y == x * x * x - Field::from(B)
+}
+
+/// `scalar * (x, y)` for a big-endian scalar, or `None` if the result is the
+/// identity. The caller must check that `(x, y)` is on the curve.
+#[allow(clippy::arithmetic_side_effects)]
+pub(crate) fn mul(scalar: &[u8; 32], x: Field, y: Field) -> Option<(Field, Field)> {
+    let mut r0 = Projective::identity();
+    let mut r1 = Projective::from_affine(x, y);
+    for byte in scalar {
+        for shift in (0..8).rev() {
+            let bit = Choice::from((byte >> shift) & 1);
+            Projective::conditional_swap(&mut r0, &mut r1, bit);
+            r1 = r0.add(&r1);
+            r0 = r0.add(&r0);
+            Projective::conditional_swap(&mut r0, &mut r1, bit);
+        }
+    }
+    r0.to_affine()
+}
+
+/// `scalar * G` for the Grumpkin generator `G`.
+pub(crate) fn mul_generator(scalar: &[u8; 32]) -> Option<(Field, Field)> {
+    mul(scalar, Field::one(), Field::from_bytes(GENERATOR_Y))
+}
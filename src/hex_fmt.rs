@@ -0,0 +1,47 @@
+//! Hex encoding shared by the `Debug`/`Display` impls, error messages, serde
+//! adapters and the proof/VK hex helpers.
+
+/// Lower-case hex encoding with a `0x` prefix.
+pub(crate) fn prefixed(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// Short `0x1234abcd…` prefix of a value for one-line summaries.
+pub(crate) fn short(bytes: &[u8]) -> String {
+    format!("{}…", prefixed(bytes.get(..4).unwrap_or(bytes)))
+}
+
+/// Decode hex, ignoring surrounding whitespace and an optional `0x`/`0X` prefix.
+pub(crate) fn decode(s: &str) -> Result<Vec<u8>, hex::FromHexError> {
+    let s = s.trim();
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    hex::decode(digits)
+}
+
+/// Serde adapter encoding `Field` values as `0x`-prefixed big-endian hex.
+pub(crate) mod field {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    use crate::bn254::Field;
+
+    pub(crate) fn serialize<S: Serializer>(
+        field: &Field,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&field.to_hex())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Field, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        let bytes = super::decode(&s).map_err(D::Error::custom)?;
+        let be: [u8; 32] = bytes.try_into().map_err(|b: Vec<u8>| {
+            D::Error::custom(format!("expected 32 bytes, got {}", b.len()))
+        })?;
+        Ok(Field::from_bytes(be))
+    }
+}
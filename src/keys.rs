@@ -3,6 +3,19 @@
 use aztec_barretenberg_rs::{
     grumpkin_derive_pubkey, schnorr_blake2s_sign, schnorr_blake2s_verify_xy,
 };
+use rand::RngCore;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::bn254::Field;
+use crate::grumpkin;
+use crate::hex_fmt;
+use crate::note_enc::{self, EncryptedNote};
+use crate::poseidon2::prf;
+use crate::types::Utxo;
+
+/// PRF indices selecting each viewing-key component derived from `vk`.
+const IVK_INDEX: u128 = 1;
+const OVK_INDEX: u128 = 2;
 
 /// Grumpkin Schnorr keypair backed by Barretenberg helpers.
 ///
@@ -11,7 +24,8 @@ use aztec_barretenberg_rs::{
 /// higher-level components can reuse existing key material.
 #[derive(Clone)]
 pub struct Keypair {
-    sk: [u8; 32],
+    /// Secret seed, wiped when the keypair is dropped.
+    sk: Zeroizing<[u8; 32]>,
     pk_x: [u8; 32],
     pk_y: [u8; 32],
 }
@@ -21,12 +35,80 @@ impl Keypair {
     pub fn from_seed(seed32: [u8; 32]) -> anyhow::Result<Self> {
         let (pk_x, pk_y) = grumpkin_derive_pubkey(&seed32)?;
         Ok(Self {
-            sk: seed32,
+            sk: Zeroizing::new(seed32),
             pk_x,
             pk_y,
         })
     }
 
+    /// Deterministic keypair for tests, derived from the seed `[index; 32]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is 0, which would be the all-zero seed.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn test_pair(index: u8) -> Self {
+        assert_ne!(index, 0, "test_pair index 0 would be the all-zero seed");
+        Self::from_seed([index; 32]).expect("derive test keypair")
+    }
+
+    /// Generate a fresh keypair from a random seed drawn from `OsRng`.
+    pub fn from_entropy() -> anyhow::Result<Self> {
+        let mut seed = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut seed);
+        let keypair = Self::from_seed(seed);
+        seed.zeroize();
+        keypair
+    }
+
+    /// Restore a keypair from a BIP-39 English mnemonic of any valid length.
+    ///
+    /// The seed is the first 32 bytes of the standard BIP-39 seed derived
+    /// with an empty passphrase, so every phrase length takes the same path.
+    #[cfg(feature = "bip39")]
+    pub fn from_mnemonic(phrase: &str) -> anyhow::Result<Self> {
+        use bip39::{Language, Mnemonic};
+
+        let mnemonic = Mnemonic::from_phrase(phrase, Language::English)
+            .map_err(|err| anyhow::anyhow!("invalid mnemonic: {err}"))?;
+        Self::from_bip39(&mnemonic)
+    }
+
+    /// Generate a fresh 24-word BIP-39 English mnemonic and its keypair.
+    ///
+    /// The phrase is the backup: [`from_mnemonic`](Self::from_mnemonic)
+    /// restores the same keypair from it. BIP-39 seed derivation is one-way,
+    /// so a keypair cannot be turned back into a phrase afterwards.
+    #[cfg(feature = "bip39")]
+    pub fn generate_mnemonic() -> anyhow::Result<(String, Self)> {
+        use bip39::{Language, Mnemonic, MnemonicType};
+
+        let mnemonic = Mnemonic::new(MnemonicType::Words24, Language::English);
+        let keypair = Self::from_bip39(&mnemonic)?;
+        Ok((mnemonic.into_phrase(), keypair))
+    }
+
+    #[cfg(feature = "bip39")]
+    fn from_bip39(mnemonic: &bip39::Mnemonic) -> anyhow::Result<Self> {
+        let seed = bip39::Seed::new(mnemonic, "");
+        let (head, _) = seed.as_bytes().split_at(32);
+        let mut seed32 = [0u8; 32];
+        seed32.copy_from_slice(head);
+        let keypair = Self::from_seed(seed32);
+        seed32.zeroize();
+        keypair
+    }
+
+    /// Return the seed this keypair was derived from, for backup.
+    ///
+    /// The seed is the secret key: treat it like one and wipe your copy with
+    /// `zeroize::Zeroize` once it has been stored. `Keypair` zeroizes its own
+    /// copy on drop. Keypairs built from external key material in future may
+    /// not have a seed and return `None`.
+    pub fn seed_bytes(&self) -> Option<[u8; 32]> {
+        Some(*self.sk)
+    }
+
     /// Return the x-only public key used by the circuits/commitments.
     pub fn public_key_xonly(&self) -> [u8; 32] {
         self.pk_x
@@ -39,7 +121,32 @@ impl Keypair {
 
     /// Sign a 32-byte prehash with Schnorr(Blake2s) over Grumpkin.
     pub fn sign_prehash(&self, msg32: [u8; 32]) -> [u8; 64] {
-        schnorr_blake2s_sign(&msg32, &self.sk).expect("schnorr sign should succeed")
+        schnorr_blake2s_sign(&msg32, &*self.sk).expect("schnorr sign should succeed")
+    }
+
+    /// Verify a signature over `msg32` against this keypair's public key.
+    pub fn verify(&self, msg32: [u8; 32], sig64: [u8; 64]) -> bool {
+        schnorr_blake2s_verify_xy(&msg32, &sig64, &self.pk_x, &self.pk_y).unwrap_or(false)
+    }
+
+    /// Derive the read-only viewing key for this keypair.
+    ///
+    /// The 32-byte secret is split into two 128-bit halves so both PRF inputs
+    /// are canonical field elements; the incoming and outgoing keys are then
+    /// PRF outputs under the viewing scalar.
+    pub fn viewing_key(&self) -> ViewingKey {
+        let (hi, lo) = self.sk.split_at(16);
+        let mut hi_bytes = [0u8; 16];
+        let mut lo_bytes = [0u8; 16];
+        hi_bytes.copy_from_slice(hi);
+        lo_bytes.copy_from_slice(lo);
+        let vk = prf(
+            Field::from(u128::from_be_bytes(hi_bytes)),
+            Field::from(u128::from_be_bytes(lo_bytes)),
+        );
+        hi_bytes.zeroize();
+        lo_bytes.zeroize();
+        ViewingKey::from_scalar(vk)
     }
 
     /// Verify a signature against the provided (x, y) public key pair.
@@ -52,3 +159,124 @@ impl Keypair {
         schnorr_blake2s_verify_xy(&msg32, &sig64, &pk_x, &pk_y).unwrap_or(false)
     }
 }
+
+// Only the public coordinates are printed; the secret key never reaches logs.
+impl std::fmt::Debug for Keypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Keypair")
+            .field("pk_x", &hex_fmt::prefixed(&self.pk_x))
+            .field("pk_y", &hex_fmt::prefixed(&self.pk_y))
+            .finish()
+    }
+}
+
+impl std::fmt::Display for Keypair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Keypair(pk_x={})", hex_fmt::prefixed(&self.pk_x))
+    }
+}
+
+/// Read-only key material derived from a [`Keypair`].
+///
+/// A viewing key lets a wallet service recognise and read notes without being
+/// able to sign transactions. `Debug` prints only the derived public parts.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ViewingKey {
+    vk: Field,
+    ovk_x: [u8; 32],
+    incoming: IncomingViewingKey,
+}
+
+impl ViewingKey {
+    fn from_scalar(vk: Field) -> Self {
+        let ivk = prf(vk, Field::from(IVK_INDEX));
+        // A PRF output of zero is the only scalar that maps to the identity.
+        let (x, y) = grumpkin::mul_generator(&ivk.to_bytes()).expect("incoming viewing scalar");
+        Self {
+            vk,
+            ovk_x: prf(vk, Field::from(OVK_INDEX)).to_bytes(),
+            incoming: IncomingViewingKey {
+                x: x.to_bytes(),
+                y: y.to_bytes(),
+            },
+        }
+    }
+
+    /// Decrypt a note encrypted to [`incoming`](Self::incoming).
+    ///
+    /// Returns `None` for notes meant for someone else or that fail the MAC.
+    pub fn try_decrypt(&self, note: &EncryptedNote) -> Option<Utxo> {
+        note_enc::decrypt(note, self.derive(IVK_INDEX))
+    }
+
+    /// PRF output under the viewing scalar for a caller-chosen `index`.
+    ///
+    /// Indices 1 and 2 are taken by the incoming and outgoing keys.
+    pub fn derive(&self, index: u128) -> Field {
+        prf(self.vk, Field::from(index))
+    }
+
+    /// Outgoing viewing key, for reading notes this wallet sent.
+    pub fn ovk_x(&self) -> [u8; 32] {
+        self.ovk_x
+    }
+
+    /// X-coordinate of the [`incoming`](Self::incoming) public key.
+    pub fn ivk_x(&self) -> [u8; 32] {
+        self.incoming.x
+    }
+
+    /// Public key senders encrypt notes to; safe to share.
+    pub fn incoming(&self) -> IncomingViewingKey {
+        self.incoming
+    }
+}
+
+/// Public incoming viewing key: a Grumpkin point senders run key agreement
+/// against when encrypting notes; see [`ViewingKey::incoming`].
+#[derive(Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct IncomingViewingKey {
+    x: [u8; 32],
+    y: [u8; 32],
+}
+
+impl IncomingViewingKey {
+    /// Rebuild a published key, checking the point is on the Grumpkin curve.
+    pub fn try_new(x: [u8; 32], y: [u8; 32]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            grumpkin::is_on_curve(Field::from_bytes(x), Field::from_bytes(y)),
+            "incoming viewing key ({}, {}) is not on the Grumpkin curve",
+            hex_fmt::prefixed(&x),
+            hex_fmt::prefixed(&y)
+        );
+        Ok(Self { x, y })
+    }
+
+    /// X-coordinate, big-endian.
+    pub fn x(&self) -> [u8; 32] {
+        self.x
+    }
+
+    /// Y-coordinate, big-endian.
+    pub fn y(&self) -> [u8; 32] {
+        self.y
+    }
+}
+
+impl std::fmt::Debug for IncomingViewingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IncomingViewingKey")
+            .field("x", &hex_fmt::prefixed(&self.x))
+            .field("y", &hex_fmt::prefixed(&self.y))
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for ViewingKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ViewingKey")
+            .field("ovk_x", &hex_fmt::prefixed(&self.ovk_x))
+            .field("ivk_x", &hex_fmt::prefixed(&self.incoming.x))
+            .finish_non_exhaustive()
+    }
+}
@@ -3,35 +3,62 @@
 #![allow(clippy::module_name_repetitions)]
 
 pub mod artifacts;
-mod barretenberg;
+pub mod barretenberg;
 pub mod batch;
+mod binprot_io;
 pub mod bn254;
 pub mod catalog;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod field;
+pub mod field_vec;
+mod grumpkin;
+mod hex_fmt;
 pub mod keys;
+pub mod merkle;
+pub mod note_enc;
 pub mod poseidon2;
+#[cfg(feature = "proptest")]
+pub mod proptest_support;
 pub mod prover;
 pub mod tx;
 pub mod types;
 
+pub use acir::native_types::{Witness, WitnessMap};
+pub use error::{CircuitError, ValidationError};
 pub use field::CircuitFieldElement;
 pub use prover::{
-    MergeInputEnc, SchnorrEnc, SpendInputEnc, TransferEnc, UtxoEnc, encode_merge_privates,
-    encode_spend_privates, fetch_batch_public_inputs, get_circuit, get_key_id, get_vk_bytes_by_id,
-    get_vk_hash_by_id, init_circuit_from_artifacts, init_default_circuits, init_embedded_catalog,
-    merge_batch_h2_by_id, prove, prove_with_abi, prove_with_all_inputs, prove_with_priv_and_pub,
-    public_outputs, regenerate_vk, verify,
+    BatchProveRequest, CircuitStats, MergeInputEnc, PROOF_FORMAT_VERSION, SchnorrEnc,
+    SpendInputEnc, TransferEnc, UtxoEnc, circuit_stats, debug_witness_map, encode_abi_inputs,
+    encode_merge_privates, encode_spend_privates, fetch_batch_public_inputs, get_circuit,
+    get_key_id, get_vk_bytes, get_vk_bytes_by_id, get_vk_hash, get_vk_hash_by_id,
+    init_circuit_from_artifacts, init_circuit_from_artifacts_with_id, init_default_circuits,
+    init_embedded_catalog, init_lazy_circuits, is_circuit_registered, list_circuits,
+    merge_batch_h2_by_id, merge_tree, proof_fields_hash, proof_from_hex, proof_to_hex, prove,
+    prove_and_get_outputs, prove_batch, prove_timed, prove_with_abi, prove_with_abi_timed,
+    prove_with_all_inputs, prove_with_all_inputs_timed, prove_with_json_abi,
+    prove_with_priv_and_pub, public_outputs, regenerate_all_vks, regenerate_vk, solve_witness,
+    unwrap_proof, unwrap_proof_version, verify, verify_batch_early_exit, verify_tx,
+    verify_with_vk_bytes, vk_from_hex, vk_to_hex, vks_are_consistent, wrap_proof,
 };
 
 pub use batch::{
-    BindingBlock, BindingLeaf, CandidateLeaf, CandidateWithRecord, LeafRecord, canonical_root_even,
-    plan_block, plan_block_from_candidates, validate_and_plan_block,
+    BindingBlock, BindingLeaf, CandidateLeaf, CandidateWithRecord, LeafRecord, apply_block_outputs,
+    canonical_root_even, compute_new_root, plan_block, plan_block_from_candidates,
+    plan_block_with_depth, split_candidates_for_block, validate_and_plan_block,
 };
-pub use keys::Keypair;
+pub use keys::{IncomingViewingKey, Keypair, ViewingKey};
+pub use merkle::IncrementalMerkleTree;
+pub use note_enc::EncryptedNote;
 pub use tx::{
-    MergeRequest, SpendRequest, merge_commitment, prove_merge, prove_spend, spend_commitments,
+    Merge3Request, MergePrepared, MergeRequest, MergeRequestView, RequestOptions, SpendPrepared,
+    SpendRequest, SpendRequestView, SplitRequest, SplitTx, UtxoOutput, merge_commitment,
+    prepare_merge, prepare_spend, prove_merge, prove_merge3, prove_spend, prove_split,
+    spend_commitments,
 };
 pub use types::{
-    Asset, MAX_ASSETS, MergeInput, MergeTx, SchnorrPublicKey, SpendInput, SpendTx,
-    TransactionOutput, Utxo, UtxoTransaction,
+    Asset, MAX_ASSETS, Merge3Tx, MergeInput, MergeOutputCommit, MergeTx, SchnorrPublicKey,
+    SpendInput, SpendOutputCommits, SpendTx, TransactionOutput, Utxo, UtxoTransaction,
+    compute_merge_output,
 };
@@ -0,0 +1,113 @@
+//! Append-only Merkle tree over UTXO commitments.
+//!
+//! Nodes are folded with the batch-merge `h2` and empty slots hold zero
+//! leaves. [`compute_new_root`](crate::batch::compute_new_root) previews the
+//! root after a block's appends without mutating the tree. Only the right-most
+//! path (the "frontier") is stored, which keeps appends at `D` hashes and
+//! memory at `O(D)` regardless of how many leaves were added.
+
+use crate::bn254::Field;
+use crate::poseidon2::h2;
+
+/// Deepest tree supported; leaf indices must fit in a `u32`.
+pub const MAX_TREE_DEPTH: usize = 32;
+
+/// Incremental Merkle tree of fixed depth `D` (at most [`MAX_TREE_DEPTH`]).
+#[derive(Clone, Debug)]
+pub struct IncrementalMerkleTree<const D: usize> {
+    /// Left sibling at each level along the next insertion path.
+    frontier: [Field; D],
+    /// Root of an all-zero subtree at each height `0..=D`.
+    zeros: Vec<Field>,
+    root: Field,
+    len: u64,
+}
+
+impl<const D: usize> IncrementalMerkleTree<D> {
+    const DEPTH_OK: () = assert!(D <= MAX_TREE_DEPTH, "Merkle tree depth must be at most 32");
+
+    /// Empty tree; its root is the all-zero subtree of height `D`.
+    pub fn new() -> Self {
+        let () = Self::DEPTH_OK;
+        let mut zeros = Vec::with_capacity(D.saturating_add(1));
+        let mut zero = Field::zero();
+        zeros.push(zero);
+        for _ in 0..D {
+            zero = h2(zero, zero);
+            zeros.push(zero);
+        }
+        Self {
+            frontier: [Field::zero(); D],
+            zeros,
+            root: zero,
+            len: 0,
+        }
+    }
+
+    /// Tree depth.
+    pub const fn depth(&self) -> usize {
+        D
+    }
+
+    /// Number of leaf slots, `2^D`.
+    // `D <= 32`, so the shift cannot overflow.
+    #[allow(clippy::arithmetic_side_effects)]
+    pub const fn capacity(&self) -> u64 {
+        1u64 << D
+    }
+
+    /// Number of leaves appended so far.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether no leaf has been appended yet.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Current root.
+    pub fn root(&self) -> Field {
+        self.root
+    }
+
+    /// Append `leaf` and return its index, or fail when the tree is full.
+    // `level < D` bounds both arrays and `len < 2^D` keeps the shifts in range.
+    #[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
+    pub fn append(&mut self, leaf: Field) -> anyhow::Result<u64> {
+        anyhow::ensure!(
+            self.len < self.capacity(),
+            "depth-{D} Merkle tree is full ({} leaves)",
+            self.capacity()
+        );
+        let index = self.len;
+        let mut path = index;
+        let mut node = leaf;
+        for level in 0..D {
+            if path & 1 == 0 {
+                self.frontier[level] = node;
+                node = h2(node, self.zeros[level]);
+            } else {
+                node = h2(self.frontier[level], node);
+            }
+            path >>= 1;
+        }
+        self.root = node;
+        self.len = index + 1;
+        Ok(index)
+    }
+
+    /// Append every leaf in order, stopping at the first one that does not fit.
+    pub fn extend(&mut self, leaves: &[Field]) -> anyhow::Result<()> {
+        for leaf in leaves {
+            self.append(*leaf)?;
+        }
+        Ok(())
+    }
+}
+
+impl<const D: usize> Default for IncrementalMerkleTree<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
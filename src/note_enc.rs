@@ -0,0 +1,169 @@
+//! Encrypted notes: UTXO data readable only by the holder of a viewing key.
+//!
+//! The sender draws a fresh ephemeral Grumpkin scalar `e`, publishes `e·G`,
+//! and runs Diffie-Hellman against the recipient's incoming viewing key
+//! `ivk·G`: both sides arrive at `e·ivk·G`, whose x-coordinate keys a Poseidon2
+//! PRF keystream that is XORed with the serialized UTXO. A Poseidon2 MAC over
+//! the plaintext lets recipients tell their notes apart from everyone else's.
+
+use std::io::{Read, Write};
+
+use anyhow::Context;
+use binprot::{BinProtRead, BinProtWrite};
+use rand::RngCore;
+use subtle::ConstantTimeEq;
+use zeroize::Zeroize;
+
+use crate::binprot_io::{read_array, read_bytes, write_bytes};
+use crate::bn254::Field;
+use crate::grumpkin;
+use crate::keys::IncomingViewingKey;
+use crate::poseidon2::{mac, prf};
+use crate::types::{Asset, MAX_ASSETS, Utxo};
+
+/// Field elements in a serialized UTXO: token and amount per slot, then the
+/// recipient key and salt.
+const NOTE_FIELDS: usize = MAX_ASSETS * 2 + 2;
+/// Ciphertext length: the encrypted fields followed by the MAC tag.
+const CIPHERTEXT_LEN: usize = (NOTE_FIELDS + 1) * 32;
+
+/// A UTXO encrypted to a recipient's incoming viewing key.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct EncryptedNote {
+    /// Encrypted UTXO fields followed by a 32-byte authentication tag.
+    pub ciphertext: Vec<u8>,
+    /// X-coordinate of the sender's one-time Grumpkin key.
+    pub ephemeral_pk_x: [u8; 32],
+    /// Y-coordinate of the sender's one-time Grumpkin key.
+    pub ephemeral_pk_y: [u8; 32],
+}
+
+impl BinProtWrite for EncryptedNote {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        write_bytes(w, &self.ciphertext)?;
+        write_bytes(w, &self.ephemeral_pk_x)?;
+        write_bytes(w, &self.ephemeral_pk_y)
+    }
+}
+
+impl BinProtRead for EncryptedNote {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        Ok(Self {
+            ciphertext: read_bytes(r)?,
+            ephemeral_pk_x: read_array(r)?,
+            ephemeral_pk_y: read_array(r)?,
+        })
+    }
+}
+
+/// Encrypt `utxo` so only the holder of the viewing key behind `recipient`
+/// can read it.
+pub fn encrypt(utxo: &Utxo, recipient: &IncomingViewingKey) -> anyhow::Result<EncryptedNote> {
+    let recipient_x = Field::from_bytes(recipient.x());
+    let recipient_y = Field::from_bytes(recipient.y());
+    anyhow::ensure!(
+        grumpkin::is_on_curve(recipient_x, recipient_y),
+        "incoming viewing key is not on the Grumpkin curve"
+    );
+
+    let mut secret = [0u8; 32];
+    rand::rngs::OsRng
+        .try_fill_bytes(&mut secret)
+        .context("drawing ephemeral note key")?;
+    let ephemeral = grumpkin::mul_generator(&secret);
+    let shared_point = grumpkin::mul(&secret, recipient_x, recipient_y);
+    secret.zeroize();
+    let (Some((ephemeral_x, ephemeral_y)), Some((shared_x, _))) = (ephemeral, shared_point) else {
+        anyhow::bail!("ephemeral note key is the identity");
+    };
+    let shared = prf(shared_x, ephemeral_x);
+
+    let plaintext = note_fields(utxo);
+    let mut ciphertext = Vec::with_capacity(CIPHERTEXT_LEN);
+    for (index, field) in plaintext.iter().enumerate() {
+        ciphertext.extend(xor(field.to_bytes(), keystream(shared, index)));
+    }
+    ciphertext.extend(mac(shared, &plaintext).to_bytes());
+
+    Ok(EncryptedNote {
+        ciphertext,
+        ephemeral_pk_x: ephemeral_x.to_bytes(),
+        ephemeral_pk_y: ephemeral_y.to_bytes(),
+    })
+}
+
+/// Decrypt `note` with the incoming viewing scalar `ivk`, returning `None` if
+/// it was not addressed to that key or has been tampered with. Callers go
+/// through [`ViewingKey::try_decrypt`](crate::keys::ViewingKey::try_decrypt).
+pub(crate) fn decrypt(note: &EncryptedNote, ivk: Field) -> Option<Utxo> {
+    if note.ciphertext.len() != CIPHERTEXT_LEN {
+        return None;
+    }
+    let ephemeral_x = Field::from_bytes(note.ephemeral_pk_x);
+    let ephemeral_y = Field::from_bytes(note.ephemeral_pk_y);
+    // Off-curve points would let a sender probe `ivk` on a weaker curve.
+    if !grumpkin::is_on_curve(ephemeral_x, ephemeral_y) {
+        return None;
+    }
+    let (shared_x, _) = grumpkin::mul(&ivk.to_bytes(), ephemeral_x, ephemeral_y)?;
+    let shared = prf(shared_x, ephemeral_x);
+
+    let (body, tag) = note.ciphertext.split_at(NOTE_FIELDS * 32);
+    let mut plaintext = [Field::zero(); NOTE_FIELDS];
+    for (index, (slot, chunk)) in plaintext.iter_mut().zip(body.chunks_exact(32)).enumerate() {
+        let bytes: [u8; 32] = chunk.try_into().ok()?;
+        *slot = Field::from_bytes(xor(bytes, keystream(shared, index)));
+    }
+    let tag: [u8; 32] = tag.try_into().ok()?;
+    // Constant-time so the comparison does not leak how much of the tag matched.
+    if !bool::from(mac(shared, &plaintext).to_bytes().ct_eq(&tag)) {
+        return None;
+    }
+    Some(utxo_from_fields(plaintext))
+}
+
+fn note_fields(utxo: &Utxo) -> [Field; NOTE_FIELDS] {
+    let mut fields = [Field::zero(); NOTE_FIELDS];
+    let assets = utxo
+        .assets
+        .iter()
+        .flat_map(|asset| [asset.token, asset.amount]);
+    let tail = [utxo.recipient_pk_x, utxo.salt];
+    for (slot, value) in fields.iter_mut().zip(assets.chain(tail)) {
+        *slot = value;
+    }
+    fields
+}
+
+fn utxo_from_fields(fields: [Field; NOTE_FIELDS]) -> Utxo {
+    let (assets, tail) = fields.split_at(MAX_ASSETS * 2);
+    let mut utxo = Utxo {
+        assets: [Asset::empty(); MAX_ASSETS],
+        recipient_pk_x: Field::zero(),
+        salt: Field::zero(),
+    };
+    for (asset, pair) in utxo.assets.iter_mut().zip(assets.chunks_exact(2)) {
+        if let [token, amount] = pair {
+            *asset = Asset {
+                token: *token,
+                amount: *amount,
+            };
+        }
+    }
+    if let [recipient_pk_x, salt] = tail {
+        utxo.recipient_pk_x = *recipient_pk_x;
+        utxo.salt = *salt;
+    }
+    utxo
+}
+
+fn keystream(shared: Field, index: usize) -> [u8; 32] {
+    prf(shared, Field::from(index as u128)).to_bytes()
+}
+
+fn xor(mut data: [u8; 32], key: [u8; 32]) -> [u8; 32] {
+    for (byte, k) in data.iter_mut().zip(key) {
+        *byte ^= k;
+    }
+    data
+}
@@ -5,7 +5,15 @@ use crate::bn254::Field;
 const LEAF_SPEND_TAG: u128 = 11;
 const LEAF_MERGE_TAG: u128 = 12;
 const BATCH_TAG: u128 = 20;
-const MANIFEST_TAG: u128 = 40;
+/// Domain tag for the keyed PRF (`PRF1`).
+pub const PRF_TAG: u128 = 30;
+/// Domain tag for the keyed MAC.
+pub const MAC_TAG: u128 = 31;
+/// Domain tag for version 1 of the block manifest hash.
+///
+/// Version 1 is the original layout, so it keeps the historic tag `40` and
+/// existing manifest hashes stay valid. A future layout must use a new tag.
+pub const MANIFEST_V1: u128 = 40;
 
 fn permute4(state: [Field; 4]) -> [Field; 4] {
     let mut buf = [0u8; 32 * 4];
@@ -35,11 +43,18 @@ fn permute4(state: [Field; 4]) -> [Field; 4] {
     out
 }
 
+/// Sponge rate: field elements absorbed per permutation.
+const RATE: usize = 3;
+
+/// Capacity-lane IV for an input of `len` field elements, `len * 2^64`.
+#[allow(clippy::arithmetic_side_effects)]
+fn sponge_iv(len: usize) -> Field {
+    Field::from((1u128) << 64) * Field::from(len as u128)
+}
+
+/// Noir's `Poseidon2::hash` sponge over `inputs`, seeded with `iv`.
 #[allow(clippy::arithmetic_side_effects, clippy::indexing_slicing)]
-pub fn hash_fields(inputs: &[Field]) -> Field {
-    const RATE: usize = 3;
-    let two_pow_64 = Field::from((1u128) << 64);
-    let iv = two_pow_64 * Field::from(inputs.len() as u128);
+fn sponge(inputs: &[Field], iv: Field) -> Field {
     let mut state = [Field::from(0u128); 4];
     state[RATE] = iv;
     let mut cache = [Field::from(0u128); RATE];
@@ -68,16 +83,91 @@ pub fn hash_fields(inputs: &[Field]) -> Field {
     state[0]
 }
 
+/// Hasher for exactly `N` inputs with the IV computed once up front.
+///
+/// Gives the same result as [`hash_fields`]; use it when hashing many inputs
+/// of the same length.
+fn sponge_fixed<const N: usize>() -> impl Fn([Field; N]) -> Field {
+    let iv = sponge_iv(N);
+    move |inputs| sponge(&inputs, iv)
+}
+
+pub fn hash_fields(inputs: &[Field]) -> Field {
+    sponge(inputs, sponge_iv(inputs.len()))
+}
+
+/// Hash `inputs` under a caller-chosen domain tag, i.e.
+/// `hash_fields(&[Field::from(tag), inputs...])`.
+pub fn hash_with_domain(tag: u128, inputs: &[Field]) -> Field {
+    let mut tagged = Vec::with_capacity(inputs.len().saturating_add(1));
+    tagged.push(Field::from(tag));
+    tagged.extend_from_slice(inputs);
+    hash_fields(&tagged)
+}
+
+/// [`hash_with_domain`] with the tag fixed at compile time.
+pub fn hash_with_domain_const<const TAG: u128>(inputs: &[Field]) -> Field {
+    hash_with_domain(TAG, inputs)
+}
+
+/// Hash a fixed number of field elements; identical to `hash_fields(&inputs)`.
+pub fn hash_n<const N: usize>(inputs: [Field; N]) -> Field {
+    hash_fields(&inputs)
+}
+
+/// Untagged two-input hash. Unlike [`h2`], no batch domain tag is prepended.
+pub fn hash2(a: Field, b: Field) -> Field {
+    hash_n([a, b])
+}
+
+#[deprecated(note = "use hash_n")]
 pub fn hash6(xs: [Field; 6]) -> Field {
-    hash_fields(&xs)
+    hash_n(xs)
 }
 
+#[deprecated(note = "use hash_n")]
 pub fn hash10(xs: [Field; 10]) -> Field {
-    hash_fields(&xs)
+    hash_n(xs)
+}
+
+/// Batch-merge parent hash of two children, taken by reference.
+///
+/// Convenient when hashing neighbours straight out of a slice.
+pub fn h2_ref(left: &Field, right: &Field) -> Field {
+    hash_fields(&[Field::from(BATCH_TAG), *left, *right])
 }
 
+/// By-value form of [`h2_ref`].
 pub fn h2(left: Field, right: Field) -> Field {
-    hash_fields(&[Field::from(BATCH_TAG), left, right])
+    h2_ref(&left, &right)
+}
+
+/// Hash each `(left, right)` pair with [`h2`].
+///
+/// Barretenberg exposes no bulk permutation, so every pair still costs one
+/// FFI call. The tag and sponge IV are built once for the whole batch.
+pub fn h2_batch(pairs: &[(Field, Field)]) -> Vec<Field> {
+    let tag = Field::from(BATCH_TAG);
+    let hash = sponge_fixed::<3>();
+    pairs
+        .iter()
+        .map(|&(left, right)| hash([tag, left, right]))
+        .collect()
+}
+
+/// Keyed pseudo-random function: `Poseidon2(PRF_TAG, key, input)`.
+pub fn prf(key: Field, input: Field) -> Field {
+    hash_fields(&[Field::from(PRF_TAG), key, input])
+}
+
+/// Apply [`prf`] under the same key to each input.
+pub fn prf_many(key: Field, inputs: &[Field]) -> Vec<Field> {
+    inputs.iter().map(|&input| prf(key, input)).collect()
+}
+
+/// Keyed MAC over a message: `Poseidon2(MAC_TAG, key, Poseidon2(message))`.
+pub fn mac(key: Field, message: &[Field]) -> Field {
+    hash_fields(&[Field::from(MAC_TAG), key, hash_fields(message)])
 }
 
 pub fn hash_spend_leaf(
@@ -100,25 +190,143 @@ pub fn hash_spend_leaf(
 }
 
 pub fn hash_merge_leaf(in_commit0: Field, in_commit1: Field, out_commit: Field) -> Field {
-    hash_fields(&[
-        Field::from(LEAF_MERGE_TAG),
-        in_commit0,
-        in_commit1,
-        out_commit,
-    ])
+    hash_merge_leaf_n(&[in_commit0, in_commit1], out_commit)
+}
+
+/// Merge leaf hash for any number of inputs: `(LEAF_MERGE_TAG, ins..., out)`.
+///
+/// The arity is not hashed separately; the sponge IV already commits to the
+/// total input length.
+pub fn hash_merge_leaf_n(in_commits: &[Field], out_commit: Field) -> Field {
+    let mut inputs = Vec::with_capacity(in_commits.len().saturating_add(2));
+    inputs.push(Field::from(LEAF_MERGE_TAG));
+    inputs.extend_from_slice(in_commits);
+    inputs.push(out_commit);
+    hash_fields(&inputs)
 }
 
+#[deprecated(note = "use hash_manifest_v1, which pins the manifest format version")]
 pub fn hash_manifest(
     block_id: u64,
     acceptance_root: Field,
     leaf_hashes_in_order: &[Field],
 ) -> Field {
-    let leaves_digest = hash_fields(leaf_hashes_in_order);
+    hash_manifest_v1(block_id, acceptance_root, leaf_hashes_in_order)
+}
+
+/// Hash a block manifest using the version 1 layout (tag [`MANIFEST_V1`]).
+pub fn hash_manifest_v1(block_id: u64, acceptance_root: Field, leaf_hashes: &[Field]) -> Field {
+    let leaves_digest = hash_fields(leaf_hashes);
     hash_fields(&[
-        Field::from(MANIFEST_TAG),
+        Field::from(MANIFEST_V1),
         Field::from(block_id as u128),
         acceptance_root,
-        Field::from(leaf_hashes_in_order.len() as u128),
+        Field::from(leaf_hashes.len() as u128),
         leaves_digest,
     ])
 }
+
+/// Regression vectors for the Barretenberg-backed Poseidon2.
+///
+/// The permutation is pinned to the reference vector for input `[0, 1, 2, 3]`
+/// published with Poseidon2 and reused by Barretenberg's and Noir's own
+/// tests. The sponge outputs were computed with an independent implementation
+/// of Noir's `Poseidon2::hash` (rate 3, capacity lane seeded with
+/// `len * 2^64`) and are hard-coded, so a change on either side of the FFI
+/// shows up here first.
+#[cfg(test)]
+mod test_vectors {
+    use super::*;
+
+    fn fe(hex_be: &str) -> Field {
+        let mut bytes = [0u8; 32];
+        hex::decode_to_slice(hex_be.trim_start_matches("0x"), &mut bytes).expect("32-byte hex");
+        Field::from_bytes(bytes)
+    }
+
+    #[track_caller]
+    fn assert_field_eq(label: &str, actual: Field, expected: Field) {
+        assert!(
+            actual == expected,
+            "{label}: got 0x{}, expected 0x{}",
+            hex::encode(actual.to_bytes()),
+            hex::encode(expected.to_bytes())
+        );
+    }
+
+    fn fields(xs: &[u128]) -> Vec<Field> {
+        xs.iter().copied().map(Field::from).collect()
+    }
+
+    #[test]
+    fn permutation_matches_reference_vector() {
+        let out = permute4([0u128, 1, 2, 3].map(Field::from));
+        let expected = [
+            "0x01bd538c2ee014ed5141b29e9ae240bf8db3fe5b9a38629a9647cf8d76c01737",
+            "0x239b62e7db98aa3a2a8f6a0d2fa1709e7a35959aa6c7034814d9daa90cbac662",
+            "0x04cbb44c61d928ed06808456bf758cbf0c18d1e15a7b6dbc8245fa7515d5e3cb",
+            "0x2e11c5cff2a22c64d01304b778d78f6998eff1ab73163a35603f54794c30847a",
+        ];
+        for (lane, (actual, expected)) in out.into_iter().zip(expected).enumerate() {
+            assert_field_eq(&format!("lane {lane}"), actual, fe(expected));
+        }
+    }
+
+    #[test]
+    fn sponge_matches_hard_coded_vectors() {
+        // Empty input, a single element, one full rate block, a spill into a
+        // second block, and several blocks.
+        let cases: [(&str, &[u128], &str); 7] = [
+            (
+                "hash([])",
+                &[],
+                "0x18dfb8dc9b82229cff974efefc8df78b1ce96d9d844236b496785c698bc6732e",
+            ),
+            (
+                "hash([0])",
+                &[0],
+                "0x2710144414c3a5f2354f4c08d52ed655b9fe253b4bf12cb9ad3de693d9b1db11",
+            ),
+            (
+                "hash([1])",
+                &[1],
+                "0x168758332d5b3e2d13be8048c8011b454590e06c44bce7f702f09103eef5a373",
+            ),
+            (
+                "hash([0, 0])",
+                &[0, 0],
+                "0x0b63a53787021a4a962a452c2921b3663aff1ffd8d5510540f8e659e782956f1",
+            ),
+            (
+                "hash([1, 2, 3])",
+                &[1, 2, 3],
+                "0x23864adb160dddf590f1d3303683ebcb914f828e2635f6e85a32f0a1aecd3dd8",
+            ),
+            (
+                "hash([1, 2, 3, 4])",
+                &[1, 2, 3, 4],
+                "0x130bf204a32cac1f0ace56c78b731aa3809f06df2731ebcf6b3464a15788b1b9",
+            ),
+            (
+                "hash(1..=10)",
+                &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+                "0x1cf91a7e72341f2804e3a5dd7c7e2b05cb27beb864104a26a4c6c39738b52947",
+            ),
+        ];
+        for (label, inputs, expected) in cases {
+            assert_field_eq(label, hash_fields(&fields(inputs)), fe(expected));
+        }
+    }
+
+    #[test]
+    fn fixed_arity_helpers_agree_with_the_sponge() {
+        let xs: [Field; 10] = std::array::from_fn(|i| Field::from((i as u128).saturating_add(1)));
+        let expected = fe("0x1cf91a7e72341f2804e3a5dd7c7e2b05cb27beb864104a26a4c6c39738b52947");
+        assert_field_eq("hash_n(1..=10)", hash_n(xs), expected);
+        assert_field_eq(
+            "hash2(0, 0)",
+            hash2(Field::zero(), Field::zero()),
+            fe("0x0b63a53787021a4a962a452c2921b3663aff1ffd8d5510540f8e659e782956f1"),
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! `proptest` strategies for the core field and UTXO types.
+//!
+//! Enabled by the `proptest` feature so downstream crates can write property
+//! tests against `any::<Field>()` and `any::<Utxo>()` without re-deriving the
+//! generators.
+
+use proptest::prelude::*;
+
+use crate::bn254::Field;
+use crate::types::{Asset, Utxo};
+
+impl Arbitrary for Field {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Uniform 32-byte draws reduced modulo the BN254 scalar field.
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        any::<[u8; 32]>()
+            .prop_map(Field::from_be_bytes_reduced)
+            .boxed()
+    }
+}
+
+impl Arbitrary for Asset {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (any::<Field>(), any::<Field>())
+            .prop_map(|(token, amount)| Asset { token, amount })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Utxo {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// Four random assets, a random recipient key, and a random salt.
+    fn arbitrary_with((): Self::Parameters) -> Self::Strategy {
+        (any::<[Asset; 4]>(), any::<Field>(), any::<Field>())
+            .prop_map(|(assets, recipient_pk_x, salt)| Utxo {
+                assets,
+                recipient_pk_x,
+                salt,
+            })
+            .boxed()
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
 use std::env;
-use std::sync::OnceLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 use acir::AcirField;
 use acir::FieldElement;
@@ -10,23 +12,48 @@ use acvm::pwg::{ACVM, ACVMStatus};
 use anyhow::Context;
 use aztec_barretenberg_rs::BarretenbergBlackBoxSolver;
 use aztec_barretenberg_rs::{
-    acvm_exec, batch_merge_h2, compile_mega, mega_public_inputs, mega_vk_hash, prove_with_id,
-    set_crs_path, verify_with_id, write_vk_mega_honk,
+    acvm_exec, batch_merge_h2, compile_mega, mega_proof_fields_hash, mega_public_inputs,
+    mega_vk_hash, prove_with_id, set_crs_path, verify_mega_honk, verify_with_id,
+    write_vk_mega_honk,
 };
+use sha2::{Digest, Sha256};
 
 use crate::barretenberg::with_bb_lock;
 use crate::bn254;
 use crate::catalog::{self, Abi, AbiType, CircuitEntry};
+use crate::field;
+use crate::hex_fmt;
+
+/// CRS directory handed to Barretenberg; `None` until first configured.
+static CRS_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
 
 fn ensure_crs() {
-    static CRS_INIT: OnceLock<()> = OnceLock::new();
-    CRS_INIT.get_or_init(|| {
+    let mut guard = CRS_DIR.lock().expect("crs mutex poisoned");
+    if guard.is_none() {
         let dir = env::var("BB_CRS_DIR")
             .ok()
             .or_else(|| dirs::home_dir().map(|h| h.join(".bb-crs").to_string_lossy().to_string()))
             .unwrap_or_else(|| ".bb-crs".to_owned());
         let _ = set_crs_path(&dir);
-    });
+        *guard = Some(PathBuf::from(dir));
+    }
+}
+
+/// Point Barretenberg at `path` for its CRS, overriding `BB_CRS_DIR`.
+///
+/// Call this before the first proving or catalog call to avoid touching the
+/// default location; calling it later re-points subsequent CRS loads.
+pub fn set_crs_directory(path: &Path) -> anyhow::Result<()> {
+    let mut guard = CRS_DIR.lock().expect("crs mutex poisoned");
+    let dir = path.to_string_lossy().to_string();
+    set_crs_path(&dir).with_context(|| format!("setting CRS directory to {path:?}"))?;
+    *guard = Some(path.to_path_buf());
+    Ok(())
+}
+
+/// Return the CRS directory in use, or `None` if it has not been configured yet.
+pub fn crs_directory() -> Option<PathBuf> {
+    CRS_DIR.lock().expect("crs mutex poisoned").clone()
 }
 
 static EMBEDDED_INIT: OnceLock<anyhow::Result<Vec<CircuitEntry>>> = OnceLock::new();
@@ -43,19 +70,55 @@ pub fn init_embedded_catalog() -> anyhow::Result<()> {
     }
 }
 
+/// Register the embedded circuits without compiling them up front.
+///
+/// The lazy counterpart of [`init_embedded_catalog`]: each circuit is compiled
+/// on its first lookup (see [`catalog::get`]).
+pub fn init_lazy_circuits() -> anyhow::Result<()> {
+    ensure_crs();
+    catalog::register_embedded_lazy()?;
+    Ok(())
+}
+
 pub fn insert_circuit(entry: CircuitEntry) {
     ensure_crs();
     catalog::insert(entry);
 }
 
+/// Look up `name`, compiling it first if it was registered lazily.
+///
+/// Returns `None` when the circuit is unknown or its compilation fails; the
+/// proving and verification entry points report the failure as an error.
 pub fn get_circuit(name: &str) -> Option<CircuitEntry> {
     catalog::get(name)
 }
 
+/// [`get_circuit`], surfacing a failed lazy compilation as an error.
+fn compiled_circuit(name: &str) -> anyhow::Result<CircuitEntry> {
+    catalog::get_compiled(name)?.ok_or_else(|| anyhow::anyhow!("circuit not initialized"))
+}
+
+/// Names of every circuit currently registered, sorted.
+///
+/// The catalog is lazily initialised: it starts empty and only holds what
+/// [`init_default_circuits`] or the `init_circuit_from_artifacts*` helpers
+/// have registered so far, so call one of those first.
+pub fn list_circuits() -> Vec<String> {
+    let mut names = catalog::all_loaded();
+    names.sort();
+    names
+}
+
+/// Whether `name` is registered in the catalog.
+///
+/// Like [`list_circuits`], this does not trigger initialisation or compile
+/// a lazily registered circuit.
+pub fn is_circuit_registered(name: &str) -> bool {
+    catalog::peek(name).is_some()
+}
+
 pub fn get_key_id(name: &str) -> anyhow::Result<[u8; 32]> {
-    get_circuit(name)
-        .map(|entry| entry.key_id)
-        .ok_or_else(|| anyhow::anyhow!("circuit not initialized"))
+    compiled_circuit(name).map(|entry| entry.key_id)
 }
 
 pub fn get_vk_bytes_by_id(vk_id: [u8; 32]) -> anyhow::Result<Vec<u8>> {
@@ -63,7 +126,7 @@ pub fn get_vk_bytes_by_id(vk_id: [u8; 32]) -> anyhow::Result<Vec<u8>> {
     if let Some(entry) = get_circuit_by_key_id(&vk_id) {
         if entry.vk.is_empty() {
             let vk = regenerate_vk(&entry.name)?;
-            let refreshed = get_circuit(&entry.name).ok_or_else(|| {
+            let refreshed = catalog::get_compiled(&entry.name)?.ok_or_else(|| {
                 anyhow::anyhow!(
                     "circuit {name} missing after regeneration",
                     name = entry.name
@@ -95,7 +158,7 @@ pub fn get_vk_bytes_by_id(vk_id: [u8; 32]) -> anyhow::Result<Vec<u8>> {
 
 fn get_circuit_by_key_id(id: &[u8; 32]) -> Option<CircuitEntry> {
     for name in catalog::all_loaded() {
-        if let Some(entry) = catalog::get(&name).filter(|entry| entry.key_id == *id) {
+        if let Some(entry) = catalog::peek(&name).filter(|entry| entry.key_id == *id) {
             return Some(entry);
         }
     }
@@ -141,17 +204,59 @@ pub fn get_vk_hash_by_id(vk_id: [u8; 32]) -> anyhow::Result<[u8; 32]> {
     ))
 }
 
+/// Verifying key bytes for a registered circuit, looked up by name.
+///
+/// Shorthand for [`get_key_id`] followed by [`get_vk_bytes_by_id`].
+pub fn get_vk_bytes(name: &str) -> anyhow::Result<Vec<u8>> {
+    get_vk_bytes_by_id(get_key_id(name)?)
+}
+
+/// Verifying key hash for a registered circuit, looked up by name.
+///
+/// Shorthand for [`get_key_id`] followed by [`get_vk_hash_by_id`].
+pub fn get_vk_hash(name: &str) -> anyhow::Result<[u8; 32]> {
+    get_vk_hash_by_id(get_key_id(name)?)
+}
+
 pub fn init_circuit_from_artifacts(
     name: &str,
     acir: &[u8],
     vk: &[u8],
     abi_json: &str,
+) -> anyhow::Result<()> {
+    init_circuit_from_artifacts_with_id(name, acir, vk, abi_json, [0u8; 32], false)
+}
+
+/// Register a circuit reusing a `key_id` obtained from an earlier `compile_mega`.
+///
+/// A non-zero `key_id` skips compilation, so Barretenberg must already hold the
+/// compiled key in this process (for example because the same ACIR was
+/// registered under another name). Pass `[0u8; 32]` to compile as usual, or set
+/// `validate_key_id` to recompile and reject an id that does not match.
+pub fn init_circuit_from_artifacts_with_id(
+    name: &str,
+    acir: &[u8],
+    vk: &[u8],
+    abi_json: &str,
+    key_id: [u8; 32],
+    validate_key_id: bool,
 ) -> anyhow::Result<()> {
     ensure_crs();
     let abi: Abi =
         serde_json::from_str(abi_json).with_context(|| format!("parsing ABI for {name}"))?;
-    let key_id =
-        with_bb_lock(|| compile_mega(acir)).with_context(|| format!("compile_mega for {name}"))?;
+    let key_id = if key_id == [0u8; 32] || validate_key_id {
+        let derived = with_bb_lock(|| compile_mega(acir))
+            .with_context(|| format!("compile_mega for {name}"))?;
+        anyhow::ensure!(
+            key_id == [0u8; 32] || key_id == derived,
+            "precomputed key id {} for {name} does not match derived id {}",
+            format_key_id(&key_id),
+            format_key_id(&derived)
+        );
+        derived
+    } else {
+        key_id
+    };
     let mut vk_vec = vk.to_vec();
     if vk_vec.is_empty() {
         let generated = with_bb_lock(|| write_vk_mega_honk(acir))?;
@@ -164,45 +269,189 @@ pub fn init_circuit_from_artifacts(
     };
     catalog::insert(CircuitEntry {
         name: name.to_string(),
+        version: String::new(),
         acir: acir.to_vec(),
+        acir_hash: Sha256::digest(acir).into(),
         vk: vk_vec,
         abi,
         key_id,
         vk_hash,
+        description: None,
     });
     Ok(())
 }
 
 pub fn regenerate_vk(name: &str) -> anyhow::Result<Vec<u8>> {
-    let entry = get_circuit(name).ok_or_else(|| anyhow::anyhow!("circuit not initialized"))?;
-    let (vk, key_id) = with_bb_lock(|| {
-        let id = compile_mega(&entry.acir)?;
-        let vk = write_vk_mega_honk(&entry.acir)?;
-        Ok::<_, anyhow::Error>((vk, id))
+    let entry = compiled_circuit(name)?;
+    let (vk, key_id) = with_bb_lock(|| compile_vk(&entry.acir))?;
+    store_regenerated_vk(name, &vk, key_id)?;
+    Ok(vk)
+}
+
+/// Regenerate the verifying key of every loaded circuit under one lock hold.
+///
+/// Returns the fresh VK bytes keyed by circuit name; the catalog is updated the
+/// same way `regenerate_vk` does it.
+pub fn regenerate_all_vks() -> anyhow::Result<HashMap<String, Vec<u8>>> {
+    let mut entries = Vec::new();
+    for name in catalog::all_loaded() {
+        entries.extend(catalog::get_compiled(&name)?);
+    }
+    let regenerated = with_bb_lock(|| {
+        entries
+            .iter()
+            .map(|entry| {
+                let (vk, key_id) = compile_vk(&entry.acir)
+                    .with_context(|| format!("regenerate vk for {}", entry.name))?;
+                Ok((entry.name.clone(), vk, key_id))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
     })?;
-    let vk_hash = aztec_barretenberg_rs::mega_vk_hash(&vk.0)?;
-    catalog::update_vk(name, &vk.0, Some(vk_hash), Some(key_id));
-    Ok(vk.0)
+    let mut out = HashMap::with_capacity(regenerated.len());
+    for (name, vk, key_id) in regenerated {
+        store_regenerated_vk(&name, &vk, key_id)?;
+        out.insert(name, vk);
+    }
+    Ok(out)
+}
+
+/// Check that every loaded circuit's cached `vk_hash` matches its VK bytes.
+///
+/// Circuits without VK bytes are skipped; a VK without a cached hash counts as
+/// inconsistent.
+pub fn vks_are_consistent() -> anyhow::Result<bool> {
+    for name in catalog::all_loaded() {
+        let Some(entry) = catalog::get_compiled(&name)? else {
+            continue;
+        };
+        if entry.vk.is_empty() {
+            continue;
+        }
+        if entry.vk_hash != Some(mega_vk_hash(&entry.vk)?) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Compile `acir` and write its verifying key; the caller holds `BB_GUARD`.
+fn compile_vk(acir: &[u8]) -> anyhow::Result<(Vec<u8>, [u8; 32])> {
+    let key_id = compile_mega(acir)?;
+    let vk = write_vk_mega_honk(acir)?;
+    Ok((vk.0, key_id))
+}
+
+fn store_regenerated_vk(name: &str, vk: &[u8], key_id: [u8; 32]) -> anyhow::Result<()> {
+    let vk_hash = mega_vk_hash(vk)?;
+    catalog::update_vk(name, vk, Some(vk_hash), Some(key_id));
+    Ok(())
+}
+
+/// Size summary of a registered circuit, derived from its ACIR alone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CircuitStats {
+    /// Number of ACIR opcodes in the main function.
+    pub opcode_count: usize,
+    /// Number of witnesses the main function allocates.
+    pub witness_count: usize,
+    /// Size of the serialized ACIR program.
+    pub acir_size_bytes: usize,
+}
+
+/// Report opcode/witness counts for `name` without invoking Barretenberg.
+pub fn circuit_stats(name: &str) -> anyhow::Result<CircuitStats> {
+    let ent = compiled_circuit(name)?;
+    let program = deserialize_program(&ent.acir)?;
+    let func = program
+        .functions
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("missing function in program"))?;
+    Ok(CircuitStats {
+        opcode_count: func.opcodes.len(),
+        witness_count: (func.current_witness_index as usize).saturating_add(1),
+        acir_size_bytes: ent.acir.len(),
+    })
+}
+
+fn deserialize_program(acir: &[u8]) -> anyhow::Result<acir::circuit::Program<FieldElement>> {
+    match acir::circuit::Program::deserialize_program(acir) {
+        Ok(p) => Ok(p),
+        Err(_) => Ok(bincode::deserialize(acir)?),
+    }
 }
 
 pub fn prove(name: &str, private_inputs: &[FieldElement]) -> anyhow::Result<Vec<u8>> {
-    let ent = get_circuit(name).ok_or_else(|| anyhow::anyhow!("circuit not initialized"))?;
+    let ent = compiled_circuit(name)?;
     let witness = acvm_exec::compute_witness_from_private_inputs(&ent.acir, private_inputs)?;
     let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness.0))?;
     Ok(proof.0)
 }
 
+/// One entry of a [`prove_batch`] call.
+#[derive(Clone, Debug)]
+pub struct BatchProveRequest {
+    /// Catalog name of the circuit to prove.
+    pub circuit_name: String,
+    /// Flattened private inputs, as accepted by [`prove`].
+    pub private_inputs: Vec<FieldElement>,
+}
+
+/// Prove several circuits while holding the Barretenberg lock only once.
+///
+/// Witnesses are solved up front without the lock; the proofs are then
+/// produced back to back so no other caller can interleave between them.
+pub fn prove_batch(requests: &[BatchProveRequest]) -> anyhow::Result<Vec<Vec<u8>>> {
+    let mut jobs = Vec::with_capacity(requests.len());
+    for req in requests {
+        let ent = catalog::get_compiled(&req.circuit_name)?
+            .ok_or_else(|| anyhow::anyhow!("circuit {} not initialized", req.circuit_name))?;
+        let witness =
+            acvm_exec::compute_witness_from_private_inputs(&ent.acir, &req.private_inputs)
+                .with_context(|| format!("solve witness for {}", req.circuit_name))?;
+        jobs.push((ent.key_id, witness.0));
+    }
+    with_bb_lock(|| {
+        jobs.iter()
+            .map(|(key_id, witness)| Ok(prove_with_id(key_id, witness)?.0))
+            .collect()
+    })
+}
+
 pub fn prove_with_priv_and_pub(
     name: &str,
     private_inputs: &[FieldElement],
     public_inputs: &[FieldElement],
 ) -> anyhow::Result<Vec<u8>> {
-    let ent = get_circuit(name).ok_or_else(|| anyhow::anyhow!("circuit not initialized"))?;
-    let program: acir::circuit::Program<FieldElement> =
-        match acir::circuit::Program::deserialize_program(&ent.acir) {
-            Ok(p) => p,
-            Err(_) => bincode::deserialize(&ent.acir)?,
-        };
+    let ent = compiled_circuit(name)?;
+    let program = deserialize_program(&ent.acir)?;
+    let witness_map = solve_priv_and_pub_witness(&program, private_inputs, public_inputs)?;
+    let witness_bytes = serialize_witness(witness_map)?;
+    let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness_bytes))?;
+    Ok(proof.0)
+}
+
+/// Run the ACVM for `name` and return the solved witness map, without proving.
+///
+/// Inputs are seeded exactly as in [`prove_with_priv_and_pub`]. The map holds
+/// every witness the circuit assigns, return values included, for tooling
+/// that works on the ACVM output directly.
+pub fn solve_witness(
+    name: &str,
+    private_inputs: &[FieldElement],
+    public_inputs: &[FieldElement],
+) -> anyhow::Result<WitnessMap<FieldElement>> {
+    let ent = compiled_circuit(name)?;
+    let program = deserialize_program(&ent.acir)?;
+    solve_priv_and_pub_witness(&program, private_inputs, public_inputs)
+}
+
+/// Run the ACVM over the main function, seeding the private and then the
+/// public parameter witnesses, each in ascending index order.
+fn solve_priv_and_pub_witness(
+    program: &acir::circuit::Program<FieldElement>,
+    private_inputs: &[FieldElement],
+    public_inputs: &[FieldElement],
+) -> anyhow::Result<WitnessMap<FieldElement>> {
     anyhow::ensure!(!program.functions.is_empty(), "empty program");
     let func = program
         .functions
@@ -211,17 +460,12 @@ pub fn prove_with_priv_and_pub(
 
     let mut initial = WitnessMap::new();
     {
-        let mut indices: Vec<u32> = func
-            .private_parameters
-            .iter()
-            .map(|w| match *w {
-                Witness(idx) => idx,
-            })
-            .collect();
-        indices.sort_unstable();
+        let indices = sorted_private_indices(func);
         anyhow::ensure!(
             private_inputs.len() <= indices.len(),
-            "too many private inputs"
+            "too many private inputs: got {}, expected {}",
+            private_inputs.len(),
+            indices.len()
         );
         for (idx, fe) in indices.iter().zip(private_inputs.iter()) {
             initial.insert(Witness(*idx), *fe);
@@ -239,7 +483,9 @@ pub fn prove_with_priv_and_pub(
         indices.sort_unstable();
         anyhow::ensure!(
             public_inputs.len() <= indices.len(),
-            "too many public inputs"
+            "too many public inputs: got {}, expected {}",
+            public_inputs.len(),
+            indices.len()
         );
         for (idx, fe) in indices.iter().zip(public_inputs.iter()) {
             initial.insert(Witness(*idx), *fe);
@@ -264,7 +510,28 @@ pub fn prove_with_priv_and_pub(
             ACVMStatus::InProgress => continue,
         }
     }
-    let witness_map = acvm.finalize();
+    Ok(acvm.finalize())
+}
+
+/// Prove `name` and read its public outputs from a single ACVM run.
+///
+/// Equivalent to calling `prove` followed by `public_outputs`, but the solved
+/// witness map is reused for both instead of executing the circuit twice.
+pub fn prove_and_get_outputs(
+    name: &str,
+    private_inputs: &[FieldElement],
+) -> anyhow::Result<(Vec<u8>, Vec<bn254::Field>)> {
+    let ent = compiled_circuit(name)?;
+    let program = deserialize_program(&ent.acir)?;
+    let map = solve_private_witness(&program, private_inputs)?;
+    let outputs = read_return_values(&program, &map)?;
+    let witness_bytes = serialize_witness(map)?;
+    let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness_bytes))?;
+    Ok((proof.0, outputs))
+}
+
+/// Encode a solved witness map as the uncompressed witness stack Barretenberg reads.
+fn serialize_witness(witness_map: WitnessMap<FieldElement>) -> anyhow::Result<Vec<u8>> {
     let stack: acir::native_types::WitnessStack<FieldElement> = witness_map.into();
     let gz = stack
         .serialize()
@@ -274,16 +541,124 @@ pub fn prove_with_priv_and_pub(
     use std::io::Read;
     dec.read_to_end(&mut witness_bytes)
         .map_err(|_| anyhow::anyhow!("gunzip witness stack"))?;
-    let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness_bytes))?;
-    Ok(proof.0)
+    Ok(witness_bytes)
+}
+
+/// Format version stamped on proofs by [`wrap_proof`] in the transaction API.
+///
+/// Bump this whenever a Barretenberg upgrade changes the proof encoding, so
+/// proofs from the old prover are rejected instead of failing obscurely.
+pub const PROOF_FORMAT_VERSION: u32 = 1;
+
+/// Length of the big-endian version tag in front of a wrapped proof.
+const PROOF_VERSION_TAG_LEN: usize = 4;
+
+/// Prefix `proof` with a 4-byte big-endian `format_version` tag.
+pub fn wrap_proof(proof: &[u8], format_version: u32) -> Vec<u8> {
+    let mut wrapped = Vec::with_capacity(proof.len().saturating_add(PROOF_VERSION_TAG_LEN));
+    wrapped.extend_from_slice(&format_version.to_be_bytes());
+    wrapped.extend_from_slice(proof);
+    wrapped
 }
 
+/// Split a [`wrap_proof`] envelope into its version tag and the bare proof.
+pub fn unwrap_proof(wrapped: &[u8]) -> anyhow::Result<(u32, &[u8])> {
+    let Some((tag, proof)) = wrapped.split_first_chunk::<PROOF_VERSION_TAG_LEN>() else {
+        anyhow::bail!(
+            "wrapped proof is {} bytes, shorter than its {PROOF_VERSION_TAG_LEN}-byte version tag",
+            wrapped.len()
+        );
+    };
+    Ok((u32::from_be_bytes(*tag), proof))
+}
+
+/// [`unwrap_proof`], rejecting envelopes whose version is not `expected`.
+pub fn unwrap_proof_version(wrapped: &[u8], expected: u32) -> anyhow::Result<&[u8]> {
+    let (version, proof) = unwrap_proof(wrapped)?;
+    anyhow::ensure!(
+        version == expected,
+        "proof format version {version} does not match the expected version {expected}"
+    );
+    Ok(proof)
+}
+
+/// Verify a bare Barretenberg proof against the registered circuit `name`.
+///
+/// Proofs taken from `SpendTx`/`MergeTx`/`SplitTx` carry a version tag; check
+/// those with [`verify_tx`].
 pub fn verify(name: &str, proof: &[u8]) -> anyhow::Result<bool> {
-    let ent = get_circuit(name).ok_or_else(|| anyhow::anyhow!("circuit not initialized"))?;
+    let ent = compiled_circuit(name)?;
     let ok = with_bb_lock(|| verify_with_id(&ent.key_id, proof))?;
     Ok(ok)
 }
 
+/// Verify a versioned proof, as produced by `prove_spend`/`prove_merge`/`prove_split`.
+///
+/// The envelope must carry [`PROOF_FORMAT_VERSION`]; a stale proof is an
+/// error rather than a failed verification.
+pub fn verify_tx(name: &str, proof: &[u8]) -> anyhow::Result<bool> {
+    verify(name, unwrap_proof_version(proof, PROOF_FORMAT_VERSION)?)
+}
+
+/// Verify bare proofs for circuit `name` in order, stopping at the first one
+/// that fails.
+///
+/// Returns `Ok(None)` when every proof verifies and `Ok(Some(i))` for the
+/// first failing index. A proof fails if Barretenberg rejects or cannot parse
+/// it. The Barretenberg lock is held once for the whole loop. Errors are
+/// reserved for an unknown circuit.
+pub fn verify_batch_early_exit(name: &str, proofs: &[&[u8]]) -> anyhow::Result<Option<usize>> {
+    let ent = compiled_circuit(name)?;
+    let first_failure = with_bb_lock(|| {
+        proofs
+            .iter()
+            .position(|proof| !matches!(verify_with_id(&ent.key_id, proof), Ok(true)))
+    });
+    Ok(first_failure)
+}
+
+/// Verify a bare `proof` against raw verifying key bytes, bypassing the catalog.
+///
+/// Useful for verifiers that receive a proof and VK over the network without
+/// having the circuit registered locally.
+pub fn verify_with_vk_bytes(proof: &[u8], vk: &[u8]) -> anyhow::Result<bool> {
+    ensure_crs();
+    let ok = with_bb_lock(|| verify_mega_honk(proof, vk))?;
+    Ok(ok)
+}
+
+/// Hash the field elements of a Mega Honk proof, as the batch merge circuit does.
+///
+/// `tag` is the domain tag the recursive verifier uses (60 for leaf proofs).
+pub fn proof_fields_hash(proof: &[u8], tag: u32) -> anyhow::Result<[u8; 32]> {
+    let hash = with_bb_lock(|| mega_proof_fields_hash(proof, tag))?;
+    Ok(hash)
+}
+
+/// Encode proof bytes as lower-case hex with a `0x` prefix.
+pub fn proof_to_hex(proof: &[u8]) -> String {
+    hex_fmt::prefixed(proof)
+}
+
+/// Decode a proof from hex, accepting an optional `0x` prefix.
+pub fn proof_from_hex(s: &str) -> anyhow::Result<Vec<u8>> {
+    hex_fmt::decode(s).context("decoding proof hex")
+}
+
+/// Encode verifying key bytes as lower-case hex with a `0x` prefix.
+pub fn vk_to_hex(vk: &[u8]) -> String {
+    hex_fmt::prefixed(vk)
+}
+
+/// Decode verifying key bytes from hex, accepting an optional `0x` prefix.
+pub fn vk_from_hex(s: &str) -> anyhow::Result<Vec<u8>> {
+    hex_fmt::decode(s).context("decoding verifying key hex")
+}
+
+/// Aggregate two bare leaf proofs into one batch node proof.
+///
+/// Proofs taken from `SpendTx`/`MergeTx`/`SplitTx` carry a version tag; strip
+/// it with [`unwrap_proof_version`] before passing them here.
 pub fn merge_batch_h2_by_id(
     left_id: [u8; 32],
     left_proof: &[u8],
@@ -302,6 +677,50 @@ pub fn merge_batch_h2_by_id(
     Ok((proof.0, merged_vk_id))
 }
 
+/// Fold leaf proofs into a single root proof with pairwise `merge_batch_h2_by_id`.
+///
+/// Leaf proofs must be bare; see [`merge_batch_h2_by_id`]. Each level pairs
+/// neighbours left to right; an odd node at a level is carried
+/// up unchanged. Merges within a level are independent and could run in
+/// parallel, but Barretenberg is not reentrant, so they run sequentially here
+/// and that level-by-level loop is the bottleneck for large batches.
+pub fn merge_tree(leaf_proofs: &[(&[u8], [u8; 32])]) -> anyhow::Result<(Vec<u8>, [u8; 32])> {
+    anyhow::ensure!(
+        !leaf_proofs.is_empty() && leaf_proofs.len() % 2 == 0,
+        "merge_tree needs a non-empty, even number of leaf proofs, got {}",
+        leaf_proofs.len()
+    );
+    let mut level: Vec<(Vec<u8>, [u8; 32])> = leaf_proofs
+        .iter()
+        .map(|(proof, vk_id)| (proof.to_vec(), *vk_id))
+        .collect();
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut nodes = level.into_iter();
+        while let Some((left_proof, left_id)) = nodes.next() {
+            match nodes.next() {
+                Some((right_proof, right_id)) => {
+                    next.push(merge_batch_h2_by_id(
+                        left_id,
+                        &left_proof,
+                        right_id,
+                        &right_proof,
+                    )?);
+                }
+                None => next.push((left_proof, left_id)),
+            }
+        }
+        level = next;
+    }
+    level
+        .pop()
+        .ok_or_else(|| anyhow::anyhow!("merge tree produced no root"))
+}
+
+/// Public inputs of a bare Mega Honk proof checked against the verifying key `vk_id`.
+///
+/// Strip the version tag from transaction proofs with [`unwrap_proof_version`]
+/// first.
 pub fn fetch_batch_public_inputs(proof: &[u8], vk_id: [u8; 32]) -> anyhow::Result<Vec<[u8; 32]>> {
     ensure_crs();
     let vk_bytes = get_vk_bytes_by_id(vk_id)?;
@@ -333,18 +752,80 @@ pub fn public_outputs(
     name: &str,
     private_inputs: &[FieldElement],
 ) -> anyhow::Result<Vec<bn254::Field>> {
-    let ent = get_circuit(name).ok_or_else(|| anyhow::anyhow!("circuit not initialized"))?;
-    let program: acir::circuit::Program<FieldElement> =
-        match acir::circuit::Program::deserialize_program(&ent.acir) {
-            Ok(p) => p,
-            Err(_) => bincode::deserialize(&ent.acir)?,
-        };
-    anyhow::ensure!(!program.functions.is_empty(), "empty program");
+    let ent = compiled_circuit(name)?;
+    let program = deserialize_program(&ent.acir)?;
+    let map = solve_private_witness(&program, private_inputs)?;
+    read_return_values(&program, &map)
+}
+
+/// Solve the circuit for `inputs_by_name` and render the witness map as JSON.
+///
+/// Each entry carries the witness index, its value as `0x`-prefixed hex, and,
+/// for private parameter witnesses, the ABI path the value came from (array
+/// elements get an `[i]` suffix). Only the ACVM runs; no proof is produced.
+pub fn debug_witness_map(
+    name: &str,
+    inputs_by_name: &HashMap<String, Vec<FE>>,
+) -> anyhow::Result<String> {
+    let ent = compiled_circuit(name)?;
+    let private_inputs = encode_abi_inputs(&ent.abi, inputs_by_name)?;
+    let program = deserialize_program(&ent.acir)?;
+    let map = solve_private_witness(&program, &private_inputs)?;
+
     let func = program
         .functions
         .first()
         .ok_or_else(|| anyhow::anyhow!("missing function in program"))?;
+    let mut labels = Vec::new();
+    for p in &ent.abi.parameters {
+        if p.visibility == "private" {
+            collect_leaf_labels(&p.name, &p.abi_type, &mut labels);
+        }
+    }
+    let param_by_index: HashMap<u32, String> = sorted_private_indices(func)
+        .into_iter()
+        .zip(labels)
+        .collect();
+
+    let witnesses: Vec<serde_json::Value> = map
+        .into_iter()
+        .map(|(Witness(idx), fe)| {
+            let mut entry = serde_json::Map::new();
+            entry.insert("index".into(), idx.into());
+            entry.insert("value".into(), hex_fmt::prefixed(&fe.to_be_bytes()).into());
+            if let Some(param) = param_by_index.get(&idx) {
+                entry.insert("param".into(), param.as_str().into());
+            }
+            serde_json::Value::Object(entry)
+        })
+        .collect();
+    let json = serde_json::json!({ "circuit": name, "witnesses": witnesses });
+    Ok(serde_json::to_string_pretty(&json)?)
+}
+
+/// Label every field element of `abi_type` with its ABI path.
+fn collect_leaf_labels(name: &str, abi_type: &AbiType, out: &mut Vec<String>) {
+    match abi_type {
+        AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => out.push(name.to_string()),
+        AbiType::Array { length, elem } => {
+            for i in 0..*length {
+                collect_leaf_labels(&format!("{name}[{i}]"), elem, out);
+            }
+        }
+        AbiType::Struct { fields } => {
+            for f in fields {
+                collect_leaf_labels(&format!("{name}.{}", f.name), &f.abi_type, out);
+            }
+        }
+        AbiType::Tuple { elements } => {
+            for (i, elem) in elements.iter().enumerate() {
+                collect_leaf_labels(&format!("{name}.{i}"), elem, out);
+            }
+        }
+    }
+}
 
+fn sorted_private_indices(func: &acir::circuit::Circuit<FieldElement>) -> Vec<u32> {
     let mut indices: Vec<u32> = func
         .private_parameters
         .iter()
@@ -353,53 +834,34 @@ pub fn public_outputs(
         })
         .collect();
     indices.sort_unstable();
-    anyhow::ensure!(
-        private_inputs.len() <= indices.len(),
-        "too many private inputs"
-    );
+    indices
+}
 
-    let mut initial = WitnessMap::new();
-    for (idx, fe) in indices.iter().zip(private_inputs.iter()) {
-        initial.insert(Witness(*idx), *fe);
-    }
+/// Run the ACVM over the main function, seeding the private parameter
+/// witnesses (in ascending index order) with `private_inputs`.
+fn solve_private_witness(
+    program: &acir::circuit::Program<FieldElement>,
+    private_inputs: &[FieldElement],
+) -> anyhow::Result<WitnessMap<FieldElement>> {
+    solve_priv_and_pub_witness(program, private_inputs, &[])
+}
 
-    let solver = BarretenbergBlackBoxSolver;
-    let mut acvm: ACVM<'_, FieldElement, _> = ACVM::new(
-        &solver,
-        &func.opcodes,
-        initial,
-        &program.unconstrained_functions,
-        &func.assert_messages,
-    );
-    loop {
-        match acvm.solve() {
-            ACVMStatus::Solved => break,
-            ACVMStatus::RequiresForeignCall(_) | ACVMStatus::RequiresAcirCall(_) => {
-                anyhow::bail!("unsupported: foreign/acir call in ACVM")
-            }
-            ACVMStatus::Failure(e) => anyhow::bail!("acvm failure: {e:?}"),
-            ACVMStatus::InProgress => continue,
-        }
-    }
-    let map = acvm.finalize();
+/// Read the main function's return witnesses out of a solved witness map.
+fn read_return_values(
+    program: &acir::circuit::Program<FieldElement>,
+    map: &WitnessMap<FieldElement>,
+) -> anyhow::Result<Vec<bn254::Field>> {
+    let func = program
+        .functions
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("missing function in program"))?;
     let mut outs = Vec::new();
     for w in func.return_values.0.iter() {
         let Witness(idx) = *w;
         let fe = map
             .get(&Witness(idx))
             .ok_or_else(|| anyhow::anyhow!("missing witness {idx}"))?;
-        let be = fe.to_be_bytes();
-        let start = be
-            .len()
-            .checked_sub(32)
-            .ok_or_else(|| anyhow::anyhow!("witness bytes shorter than 32"))?;
-        let tail = be
-            .get(start..)
-            .ok_or_else(|| anyhow::anyhow!("missing 32-byte tail"))?;
-        anyhow::ensure!(tail.len() == 32, "expected 32-byte field tail");
-        let mut b32 = [0u8; 32];
-        b32.copy_from_slice(tail);
-        outs.push(bn254::Field::from_bytes(b32));
+        outs.push(field::from_acir_field(*fe));
     }
     Ok(outs)
 }
@@ -500,103 +962,145 @@ pub fn prove_with_abi(
     name: &str,
     inputs_by_name: &HashMap<String, Vec<FE>>,
 ) -> anyhow::Result<Vec<u8>> {
-    let ent = get_circuit(name).ok_or_else(|| anyhow::anyhow!("circuit not initialized"))?;
-    fn push_param(
-        acc: &mut Vec<FE>,
-        abi_type: &AbiType,
-        name: &str,
-        inputs_by_name: &HashMap<String, Vec<FE>>,
-    ) -> anyhow::Result<()> {
-        match abi_type {
-            AbiType::Field => {
-                let v = inputs_by_name
-                    .get(name)
-                    .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
-                anyhow::ensure!(v.len() == 1, "param {name} expects 1 field element");
-                if let Some(x) = v.first() {
-                    acc.push(*x);
-                } else {
-                    anyhow::bail!("param {name} expects 1 element");
-                }
-            }
-            AbiType::Array { length, elem } => {
-                let v = inputs_by_name
-                    .get(name)
-                    .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
-                anyhow::ensure!(
-                    v.len() == *length,
-                    "param {name} expects array length {length}, got {}",
-                    v.len()
-                );
-                match &**elem {
-                    AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => {
-                        acc.extend_from_slice(v);
-                    }
-                    AbiType::Array { .. } => {
-                        anyhow::bail!("nested arrays not supported in this helper: {name}");
-                    }
-                    AbiType::Struct { .. } => {
-                        anyhow::bail!("arrays of structs not supported in this helper: {name}");
-                    }
-                }
-            }
-            AbiType::Integer { .. } | AbiType::Boolean => {
-                let v = inputs_by_name
-                    .get(name)
-                    .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
-                anyhow::ensure!(v.len() == 1, "param {name} expects 1 element");
-                if let Some(x) = v.first() {
-                    acc.push(*x);
-                } else {
-                    anyhow::bail!("param {name} expects 1 element");
-                }
-            }
-            AbiType::Struct { fields } => {
-                for f in fields {
-                    let child = format!("{name}.{}", f.name);
-                    push_param(acc, &f.abi_type, &child, inputs_by_name)?;
-                }
-            }
+    let ent = compiled_circuit(name)?;
+    let private_inputs = encode_abi_inputs(&ent.abi, inputs_by_name)?;
+
+    let witness = acvm_exec::compute_witness_from_private_inputs(&ent.acir, &private_inputs)?;
+    let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness.0))?;
+    Ok(proof.0)
+}
+
+/// [`prove_with_abi`] for inputs supplied as a JSON object.
+///
+/// Keys are the ABI paths [`encode_abi_inputs`] expects. Array values give one
+/// element per entry and scalars a single element; each element may be a
+/// `0x`-prefixed hex string or a decimal string below the field modulus, a
+/// non-negative integer, or a boolean. Strings at or above the modulus are
+/// rejected rather than reduced.
+pub fn prove_with_json_abi(name: &str, inputs_json: &str) -> anyhow::Result<Vec<u8>> {
+    let raw: HashMap<String, serde_json::Value> =
+        serde_json::from_str(inputs_json).context("parsing ABI inputs JSON")?;
+    let mut inputs_by_name = HashMap::with_capacity(raw.len());
+    for (key, value) in raw {
+        let elems = match &value {
+            serde_json::Value::Array(items) => items.iter().map(fe_from_json).collect(),
+            scalar => fe_from_json(scalar).map(|fe| vec![fe]),
         }
-        Ok(())
+        .with_context(|| format!("decoding input {key}"))?;
+        inputs_by_name.insert(key, elems);
     }
+    prove_with_abi(name, &inputs_by_name)
+}
 
-    let mut private_inputs: Vec<FE> = Vec::new();
-    for p in &ent.abi.parameters {
-        if p.visibility == "private" {
-            push_param(&mut private_inputs, &p.abi_type, &p.name, inputs_by_name)?;
+fn fe_from_json(value: &serde_json::Value) -> anyhow::Result<FE> {
+    match value {
+        serde_json::Value::String(s) => {
+            let s = s.trim();
+            let be32 = if let Some(digits) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+                anyhow::ensure!(digits.len() <= 64, "hex value {s} exceeds 32 bytes");
+                let mut be32 = [0u8; 32];
+                hex::decode_to_slice(format!("{digits:0>64}"), &mut be32)
+                    .with_context(|| format!("invalid hex value {s}"))?;
+                be32
+            } else {
+                be32_from_decimal(s)?
+            };
+            let field = bn254::Field::from_bytes_checked(be32)
+                .with_context(|| format!("input value {s}"))?;
+            Ok(fe_from_field(&field))
+        }
+        serde_json::Value::Number(n) => {
+            let v = n
+                .as_u64()
+                .ok_or_else(|| anyhow::anyhow!("expected a non-negative integer, got {n}"))?;
+            Ok(FE::from(u128::from(v)))
         }
+        serde_json::Value::Bool(b) => Ok(FE::from(u128::from(*b))),
+        other => anyhow::bail!("unsupported input value {other}"),
     }
+}
 
-    let witness = acvm_exec::compute_witness_from_private_inputs(&ent.acir, &private_inputs)?;
-    let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness.0))?;
-    Ok(proof.0)
+/// Parse an unsigned decimal string into 32 big-endian bytes.
+#[allow(clippy::arithmetic_side_effects)]
+fn be32_from_decimal(s: &str) -> anyhow::Result<[u8; 32]> {
+    anyhow::ensure!(
+        !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()),
+        "invalid decimal value {s}"
+    );
+    let mut be32 = [0u8; 32];
+    for digit in s.bytes() {
+        let mut carry = u16::from(digit - b'0');
+        for byte in be32.iter_mut().rev() {
+            let [hi, lo] = (u16::from(*byte) * 10 + carry).to_be_bytes();
+            *byte = lo;
+            carry = u16::from(hi);
+        }
+        anyhow::ensure!(carry == 0, "decimal value {s} exceeds 256 bits");
+    }
+    Ok(be32)
 }
 
 pub fn prove_with_all_inputs(
     name: &str,
     inputs_by_name: &HashMap<String, Vec<FE>>,
 ) -> anyhow::Result<Vec<u8>> {
-    let ent = get_circuit(name).ok_or_else(|| anyhow::anyhow!("circuit not initialized"))?;
-    fn push_param(
-        acc: &mut Vec<FE>,
-        abi_type: &AbiType,
-        name: &str,
-        inputs_by_name: &HashMap<String, Vec<FE>>,
-    ) -> anyhow::Result<()> {
-        match abi_type {
-            AbiType::Field => {
-                let v = inputs_by_name
-                    .get(name)
-                    .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
-                anyhow::ensure!(v.len() == 1, "param {name} expects 1 field element");
-                if let Some(x) = v.first() {
-                    acc.push(*x);
-                } else {
-                    anyhow::bail!("param {name} expects 1 element");
-                }
+    let ent = compiled_circuit(name)?;
+    ent.abi.validate_inputs(inputs_by_name).map_err(|keys| {
+        anyhow::anyhow!(
+            "missing or wrong-length inputs for {name}: {}",
+            keys.join(", ")
+        )
+    })?;
+    let all_inputs = encode_abi_inputs(&ent.abi, inputs_by_name)?;
+
+    let witness = acvm_exec::compute_witness_from_private_inputs(&ent.acir, &all_inputs)?;
+    let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness.0))?;
+    Ok(proof.0)
+}
+
+/// Flatten named inputs into the private witness order declared by `abi`.
+///
+/// Keys follow the Noir struct paths (`input.schnorr.pk_x`). Arrays of scalars
+/// are a single key; arrays of structs or arrays are addressed per element
+/// with an index suffix, e.g. `input.paths[0].hash` or `input.matrix[1]`.
+/// Tuple elements are named by position, e.g. `input.pair.0`.
+pub fn encode_abi_inputs(
+    abi: &Abi,
+    inputs_by_name: &HashMap<String, Vec<FE>>,
+) -> anyhow::Result<Vec<FE>> {
+    let mut private_inputs: Vec<FE> = Vec::new();
+    for p in &abi.parameters {
+        if p.visibility == "private" {
+            push_param(&mut private_inputs, &p.abi_type, &p.name, inputs_by_name)?;
+        }
+    }
+    Ok(private_inputs)
+}
+
+fn push_param(
+    acc: &mut Vec<FE>,
+    abi_type: &AbiType,
+    name: &str,
+    inputs_by_name: &HashMap<String, Vec<FE>>,
+) -> anyhow::Result<()> {
+    match abi_type {
+        AbiType::Field => {
+            let v = inputs_by_name
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
+            anyhow::ensure!(
+                v.len() == 1,
+                "param {name} expects 1 field element, got {}",
+                v.len()
+            );
+            if let Some(x) = v.first() {
+                acc.push(*x);
+            } else {
+                anyhow::bail!("param {name} expects 1 element");
             }
-            AbiType::Array { length, elem } => {
+        }
+        AbiType::Array { length, elem } => match &**elem {
+            AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => {
                 let v = inputs_by_name
                     .get(name)
                     .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
@@ -605,47 +1109,72 @@ pub fn prove_with_all_inputs(
                     "param {name} expects array length {length}, got {}",
                     v.len()
                 );
-                match &**elem {
-                    AbiType::Field | AbiType::Integer { .. } | AbiType::Boolean => {
-                        acc.extend_from_slice(v);
-                    }
-                    AbiType::Array { .. } => {
-                        anyhow::bail!("nested arrays not supported in this helper: {name}");
-                    }
-                    AbiType::Struct { .. } => {
-                        anyhow::bail!("arrays of structs not supported in this helper: {name}");
-                    }
-                }
+                acc.extend_from_slice(v);
             }
-            AbiType::Integer { .. } | AbiType::Boolean => {
-                let v = inputs_by_name
-                    .get(name)
-                    .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
-                anyhow::ensure!(v.len() == 1, "param {name} expects 1 element");
-                if let Some(x) = v.first() {
-                    acc.push(*x);
-                } else {
-                    anyhow::bail!("param {name} expects 1 element");
+            AbiType::Array { .. } | AbiType::Struct { .. } | AbiType::Tuple { .. } => {
+                for i in 0..*length {
+                    let child = format!("{name}[{i}]");
+                    push_param(acc, elem, &child, inputs_by_name)?;
                 }
             }
-            AbiType::Struct { fields } => {
-                for f in fields {
-                    let child = format!("{name}.{}", f.name);
-                    push_param(acc, &f.abi_type, &child, inputs_by_name)?;
-                }
+        },
+        AbiType::Integer { .. } | AbiType::Boolean => {
+            let v = inputs_by_name
+                .get(name)
+                .ok_or_else(|| anyhow::anyhow!(format!("missing input for param {name}")))?;
+            anyhow::ensure!(
+                v.len() == 1,
+                "param {name} expects 1 element, got {}",
+                v.len()
+            );
+            if let Some(x) = v.first() {
+                acc.push(*x);
+            } else {
+                anyhow::bail!("param {name} expects 1 element");
             }
         }
-        Ok(())
-    }
-
-    let mut all_inputs: Vec<FE> = Vec::new();
-    for p in &ent.abi.parameters {
-        if p.visibility == "private" {
-            push_param(&mut all_inputs, &p.abi_type, &p.name, inputs_by_name)?;
+        AbiType::Struct { fields } => {
+            for f in fields {
+                let child = format!("{name}.{}", f.name);
+                push_param(acc, &f.abi_type, &child, inputs_by_name)?;
+            }
+        }
+        AbiType::Tuple { elements } => {
+            for (i, elem) in elements.iter().enumerate() {
+                let child = format!("{name}.{i}");
+                push_param(acc, elem, &child, inputs_by_name)?;
+            }
         }
     }
+    Ok(())
+}
 
-    let witness = acvm_exec::compute_witness_from_private_inputs(&ent.acir, &all_inputs)?;
-    let proof = with_bb_lock(|| prove_with_id(&ent.key_id, &witness.0))?;
-    Ok(proof.0)
+/// `prove` plus the wall-clock time it took.
+pub fn prove_timed(
+    name: &str,
+    private_inputs: &[FieldElement],
+) -> anyhow::Result<(Vec<u8>, Duration)> {
+    let started = Instant::now();
+    let proof = prove(name, private_inputs)?;
+    Ok((proof, started.elapsed()))
+}
+
+/// `prove_with_abi` plus the wall-clock time it took.
+pub fn prove_with_abi_timed(
+    name: &str,
+    inputs_by_name: &HashMap<String, Vec<FE>>,
+) -> anyhow::Result<(Vec<u8>, Duration)> {
+    let started = Instant::now();
+    let proof = prove_with_abi(name, inputs_by_name)?;
+    Ok((proof, started.elapsed()))
+}
+
+/// `prove_with_all_inputs` plus the wall-clock time it took.
+pub fn prove_with_all_inputs_timed(
+    name: &str,
+    inputs_by_name: &HashMap<String, Vec<FE>>,
+) -> anyhow::Result<(Vec<u8>, Duration)> {
+    let started = Instant::now();
+    let proof = prove_with_all_inputs(name, inputs_by_name)?;
+    Ok((proof, started.elapsed()))
 }
@@ -18,16 +18,46 @@ use acir_field::FieldElement as FE;
 use rand::RngCore;
 
 use crate::bn254::Field;
-use crate::keys::Keypair;
+use crate::error::CircuitError;
+use crate::keys::{IncomingViewingKey, Keypair};
+use crate::note_enc;
 use crate::poseidon2::hash_fields;
 use crate::prover;
-use crate::types::{Asset, MergeInput, MergeTx, SpendInput, TransactionOutput, Utxo};
+use crate::types::{
+    Asset, MAX_ASSETS, Merge3Tx, MergeInput, MergeOutputCommit, MergeTx, SchnorrPublicKey,
+    SpendInput, SpendOutputCommits, TransactionOutput, Utxo, compute_merge_output,
+};
 
 const SPEND_CIRCUIT: &str = "utxo_spend";
 const MERGE_CIRCUIT: &str = "utxo_merge";
+/// Prototype split circuit; its ACIR/ABI are not embedded yet.
+const SPLIT_CIRCUIT: &str = "utxo_split";
 
 type EnsureUniqueFn = dyn Fn(&[Field]) -> anyhow::Result<bool>;
 
+/// Default [`RequestOptions::max_attempts`].
+pub const DEFAULT_MAX_SALT_ATTEMPTS: u32 = 100;
+
+/// Salt retry settings shared by [`SpendRequest`], [`MergeRequest`] and
+/// [`Merge3Request`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RequestOptions {
+    /// Salts tried before giving up on `ensure_unique`.
+    pub max_attempts: u32,
+    /// Report the number of salts tried in `SpendTx::salt_attempts`; when off
+    /// the count is left at 0. Merges have no such field and ignore it.
+    pub record_attempts: bool,
+}
+
+impl Default for RequestOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_SALT_ATTEMPTS,
+            record_attempts: true,
+        }
+    }
+}
+
 /// Lazily register the named circuit in the embedded catalog.
 ///
 /// The first caller triggers `init_default_circuits`, which loads the ACIR,
@@ -80,15 +110,36 @@ pub struct SpendRequest<'a> {
     /// Input payload for the consumed UTXO.
     pub input: SpendInput,
     /// Token to transfer to the receiver.
+    ///
+    /// The receiver output holds it in the slot it occupies in the input;
+    /// `utxo_spend` cannot move it to a different slot.
     pub transfer_token: Field,
     /// Amount to transfer to the receiver.
     pub transfer_amount: Field,
     /// Amount to pay as fee (deducted from slot 0 / remainder output).
+    ///
+    /// `utxo_spend` charges the fee against slot 0 whatever token it holds.
     pub fee_amount: Field,
+    /// Catalog name of the circuit to prove with (defaults to `utxo_spend`).
+    pub circuit_name: Option<&'static str>,
     /// Optional uniqueness check for the output commitments.
     pub ensure_unique: Option<&'a EnsureUniqueFn>,
+    /// Salt attempt budget for `ensure_unique` and whether to report the count.
+    pub options: RequestOptions,
     /// Run `verify` after proving; useful during tests and debugging.
     pub verify_proof: bool,
+    /// Verifying key for `verify_proof`, skipping the catalog lookup.
+    ///
+    /// Handy when many proofs are checked against a VK the caller already
+    /// holds. `None` verifies against the catalog entry for the circuit.
+    pub verifier_vk_bytes: Option<&'a [u8]>,
+    /// Recipient's incoming viewing key; when set, the returned `SpendTx`
+    /// carries [`EncryptedNote`](crate::note_enc::EncryptedNote)s for both
+    /// outputs.
+    ///
+    /// The receiver note is encrypted to this key and the remainder note to
+    /// the signer's own viewing key.
+    pub recipient_ivk: Option<IncomingViewingKey>,
 }
 
 /// High-level input for a merge proof.
@@ -103,34 +154,322 @@ pub struct MergeRequest<'a> {
     pub out_amounts: [Field; 4],
     /// Optional salt override (random when `None`).
     pub out_salt: Option<Field>,
+    /// Catalog name of the circuit to prove with (defaults to `utxo_merge`).
+    pub circuit_name: Option<&'static str>,
     /// Optional uniqueness check for the output commitment.
     pub ensure_unique: Option<&'a EnsureUniqueFn>,
+    /// Salt attempt budget for `ensure_unique`.
+    pub options: RequestOptions,
     /// Run `verify` after proving; useful during tests and debugging.
     pub verify_proof: bool,
 }
 
+/// Owned, loggable snapshot of a [`SpendRequest`].
+///
+/// The signer is reduced to its public key and the uniqueness closure to a
+/// flag, so the view can be cloned, printed, and serialised.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SpendRequestView {
+    /// Public key of the signing keypair.
+    pub signer: SchnorrPublicKey,
+    /// Receiver public key x-coordinate.
+    pub recipient_pk_x: [u8; 32],
+    /// Input payload for the consumed UTXO.
+    pub input: SpendInput,
+    /// Token to transfer to the receiver.
+    pub transfer_token: Field,
+    /// Amount to transfer to the receiver.
+    pub transfer_amount: Field,
+    /// Amount to pay as fee.
+    pub fee_amount: Field,
+    /// Circuit name override, if any.
+    pub circuit_name: Option<String>,
+    /// Whether the request carried an `ensure_unique` check.
+    pub has_unique_check: bool,
+    /// Salt retry settings.
+    #[serde(default)]
+    pub options: RequestOptions,
+    /// Whether the proof is verified after proving.
+    pub verify_proof: bool,
+    /// Length of the caller-supplied verifying key, if any.
+    #[serde(default)]
+    pub verifier_vk_len: Option<usize>,
+    /// Incoming viewing key the receiver note is encrypted to, if any.
+    #[serde(default)]
+    pub recipient_ivk: Option<IncomingViewingKey>,
+}
+
+impl SpendRequest<'_> {
+    /// Snapshot the request for logging or error reporting.
+    pub fn to_view(&self) -> SpendRequestView {
+        let (pk_x, pk_y) = self.signer.public_key_xy();
+        SpendRequestView {
+            signer: SchnorrPublicKey::new(pk_x, pk_y),
+            recipient_pk_x: self.recipient_pk_x,
+            input: self.input.clone(),
+            transfer_token: self.transfer_token,
+            transfer_amount: self.transfer_amount,
+            fee_amount: self.fee_amount,
+            circuit_name: self.circuit_name.map(str::to_owned),
+            has_unique_check: self.ensure_unique.is_some(),
+            options: self.options,
+            verify_proof: self.verify_proof,
+            verifier_vk_len: self.verifier_vk_bytes.map(<[u8]>::len),
+            recipient_ivk: self.recipient_ivk,
+        }
+    }
+
+    /// Partial equality: compares every field except `ensure_unique`.
+    ///
+    /// Closures cannot be compared, so two requests that differ only in their
+    /// uniqueness check are reported equal. Signers are compared by public key.
+    /// Pair with [`has_unique_check`](Self::has_unique_check) when the presence
+    /// of a check matters.
+    pub fn eq_ignoring_closure(&self, other: &Self) -> bool {
+        let Self {
+            signer,
+            recipient_pk_x,
+            input,
+            transfer_token,
+            transfer_amount,
+            fee_amount,
+            circuit_name,
+            ensure_unique: _,
+            options,
+            verify_proof,
+            verifier_vk_bytes,
+            recipient_ivk,
+        } = self;
+        signer.public_key_xy() == other.signer.public_key_xy()
+            && *recipient_pk_x == other.recipient_pk_x
+            && *input == other.input
+            && *transfer_token == other.transfer_token
+            && *transfer_amount == other.transfer_amount
+            && *fee_amount == other.fee_amount
+            && *circuit_name == other.circuit_name
+            && *options == other.options
+            && *verify_proof == other.verify_proof
+            && *verifier_vk_bytes == other.verifier_vk_bytes
+            && *recipient_ivk == other.recipient_ivk
+    }
+
+    /// Whether the request carries an `ensure_unique` check.
+    pub fn has_unique_check(&self) -> bool {
+        self.ensure_unique.is_some()
+    }
+}
+
+/// Owned, loggable snapshot of a [`MergeRequest`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MergeRequestView {
+    /// Public key of the signing keypair.
+    pub signer: SchnorrPublicKey,
+    /// Input payloads for the two consumed UTXOs.
+    pub inputs: [MergeInput; 2],
+    /// Token identifiers for the merged output.
+    pub out_tokens: [Field; 4],
+    /// Amounts for the merged output.
+    pub out_amounts: [Field; 4],
+    /// Salt override for the output, if any.
+    pub out_salt: Option<Field>,
+    /// Circuit name override, if any.
+    pub circuit_name: Option<String>,
+    /// Whether the request carried an `ensure_unique` check.
+    pub has_unique_check: bool,
+    /// Salt retry settings.
+    #[serde(default)]
+    pub options: RequestOptions,
+    /// Whether the proof is verified after proving.
+    pub verify_proof: bool,
+}
+
+impl<'a> MergeRequest<'a> {
+    /// Merge two UTXOs owned by `signer` into one with the summed amounts.
+    ///
+    /// The output arrays come from [`compute_merge_output`], so the inputs
+    /// must hold the same token in every slot. The remaining options take
+    /// their defaults: a random salt, the `utxo_merge` circuit, no uniqueness
+    /// check, and no verification after proving.
+    pub fn from_utxos(signer: &'a Keypair, in0: Utxo, in1: Utxo) -> anyhow::Result<Self> {
+        let (out_tokens, out_amounts) = compute_merge_output(&in0, &in1)?;
+        let (pk_x, pk_y) = signer.public_key_xy();
+        let signer_pk = SchnorrPublicKey::new(pk_x, pk_y);
+        Ok(Self {
+            signer,
+            inputs: [
+                MergeInput::new(in0, signer_pk),
+                MergeInput::new(in1, signer_pk),
+            ],
+            out_tokens,
+            out_amounts,
+            out_salt: None,
+            circuit_name: None,
+            ensure_unique: None,
+            options: RequestOptions::default(),
+            verify_proof: false,
+        })
+    }
+
+    /// Snapshot the request for logging or error reporting.
+    pub fn to_view(&self) -> MergeRequestView {
+        let (pk_x, pk_y) = self.signer.public_key_xy();
+        MergeRequestView {
+            signer: SchnorrPublicKey::new(pk_x, pk_y),
+            inputs: self.inputs.clone(),
+            out_tokens: self.out_tokens,
+            out_amounts: self.out_amounts,
+            out_salt: self.out_salt,
+            circuit_name: self.circuit_name.map(str::to_owned),
+            has_unique_check: self.ensure_unique.is_some(),
+            options: self.options,
+            verify_proof: self.verify_proof,
+        }
+    }
+
+    /// Partial equality: compares every field except `ensure_unique`.
+    ///
+    /// See [`SpendRequest::eq_ignoring_closure`]; signers are compared by
+    /// public key.
+    pub fn eq_ignoring_closure(&self, other: &Self) -> bool {
+        let Self {
+            signer,
+            inputs,
+            out_tokens,
+            out_amounts,
+            out_salt,
+            circuit_name,
+            ensure_unique: _,
+            options,
+            verify_proof,
+        } = self;
+        signer.public_key_xy() == other.signer.public_key_xy()
+            && *inputs == other.inputs
+            && *out_tokens == other.out_tokens
+            && *out_amounts == other.out_amounts
+            && *out_salt == other.out_salt
+            && *circuit_name == other.circuit_name
+            && *options == other.options
+            && *verify_proof == other.verify_proof
+    }
+
+    /// Whether the request carries an `ensure_unique` check.
+    pub fn has_unique_check(&self) -> bool {
+        self.ensure_unique.is_some()
+    }
+}
+
+/// High-level input for a three-input merge built from two chained merge proofs.
+pub struct Merge3Request<'a> {
+    /// Schnorr keypair that authorises both merges.
+    pub signer: &'a Keypair,
+    /// Input payloads for the three consumed UTXOs.
+    pub inputs: [MergeInput; 3],
+    /// Token identifiers for the final merged output.
+    pub out_tokens: [Field; 4],
+    /// Amounts for the final merged output.
+    pub out_amounts: [Field; 4],
+    /// Optional salt override for the final output (random when `None`).
+    pub out_salt: Option<Field>,
+    /// Optional uniqueness check applied to both merge outputs.
+    pub ensure_unique: Option<&'a EnsureUniqueFn>,
+    /// Salt attempt budget applied to each merge.
+    pub options: RequestOptions,
+    /// Run `verify` after each proof.
+    pub verify_proof: bool,
+}
+
 /// Build the Noir ABI for a spend, generate the proof, and return a rich result.
 ///
 /// Steps:
-/// 1. Derive the receiver and remainder UTXOs plus their Poseidon2 commitments.
-/// 2. Populate the Noir ABI map (`input.*` keys) so
-///    `prove_with_all_inputs` can translate the values into witness indices.
-/// 3. Sign the canonical digest, inject the signature into the ABI, and call
-///    Barretenberg to obtain the proof bytes.
-/// 4. Reconstruct the typed outputs and bundle everything into `SpendTx`.
-#[allow(clippy::indexing_slicing, clippy::arithmetic_side_effects)]
+/// 1. [`prepare_spend`] derives the outputs, commitments, and signed ABI map.
+/// 2. Barretenberg proves the circuit from that map.
+/// 3. The typed outputs (and optional encrypted notes) are bundled into `SpendTx`.
 pub fn prove_spend(req: SpendRequest<'_>) -> anyhow::Result<crate::types::SpendTx> {
-    ensure_circuit_loaded(SPEND_CIRCUIT)?;
+    let circuit = req.circuit_name.unwrap_or(SPEND_CIRCUIT);
+    ensure_circuit_loaded(circuit)?;
+    let prepared = prepare_spend(&req)?;
+
+    let proof = prover::prove_with_all_inputs(circuit, &prepared.abi_inputs)?;
+    if req.verify_proof {
+        let verified = match req.verifier_vk_bytes {
+            Some(vk) => prover::verify_with_vk_bytes(&proof, vk)?,
+            None => prover::verify(circuit, &proof)?,
+        };
+        anyhow::ensure!(verified, "generated spend proof failed verification");
+    }
+    let proof = prover::wrap_proof(&proof, prover::PROOF_FORMAT_VERSION);
+
+    let SpendPrepared {
+        receiver,
+        remainder,
+        receiver_commit,
+        remainder_commit,
+        digest,
+        msg32,
+        signature,
+        salt_attempts,
+        ..
+    } = prepared;
+    let (receiver_note, remainder_note) = match &req.recipient_ivk {
+        Some(recipient_ivk) => (
+            Some(note_enc::encrypt(&receiver, recipient_ivk)?),
+            Some(note_enc::encrypt(
+                &remainder,
+                &req.signer.viewing_key().incoming(),
+            )?),
+        ),
+        None => (None, None),
+    };
+
+    Ok(crate::types::SpendTx {
+        input: req.input,
+        outputs: TransactionOutput::Spend {
+            receiver,
+            remainder,
+        },
+        expected_out_commits: SpendOutputCommits {
+            receiver: receiver_commit,
+            remainder: remainder_commit,
+        },
+        proof,
+        transfer_token: req.transfer_token,
+        transfer_amount: req.transfer_amount,
+        fee_amount: req.fee_amount,
+        signature,
+        msg32,
+        digest,
+        receiver_note,
+        remainder_note,
+        salt_attempts: if req.options.record_attempts {
+            salt_attempts
+        } else {
+            0
+        },
+    })
+}
+
+/// Validate a spend request and build everything the prover needs.
+///
+/// Derives the receiver and remainder UTXOs and their commitments, fills the
+/// Noir ABI map (`input.*` keys), and signs the digest, so the returned
+/// `abi_inputs` can go straight to `prover::prove_with_all_inputs`. Nothing is
+/// proved; the circuit does not even need to be loaded.
+#[allow(clippy::indexing_slicing, clippy::arithmetic_side_effects)]
+pub fn prepare_spend(req: &SpendRequest<'_>) -> anyhow::Result<SpendPrepared> {
     let SpendRequest {
         signer,
         recipient_pk_x,
-        input,
+        ref input,
         transfer_token,
         transfer_amount,
         fee_amount,
+        circuit_name: _,
         ensure_unique,
-        verify_proof,
-    } = req;
+        options,
+        verify_proof: _,
+        verifier_vk_bytes: _,
+        recipient_ivk: _,
+    } = *req;
 
     let (sender_pkx, sender_pky) = signer.public_key_xy();
 
@@ -142,6 +481,7 @@ pub fn prove_spend(req: SpendRequest<'_>) -> anyhow::Result<crate::types::SpendT
         input.utxo.recipient_pk_x == input.signer.pk_x_field(),
         "spend input utxo recipient key does not match signer key",
     );
+    input.validate()?;
 
     // Precompute input token/amount arrays
     let in_tokens = [
@@ -199,8 +539,10 @@ pub fn prove_spend(req: SpendRequest<'_>) -> anyhow::Result<crate::types::SpendT
 
     let mut receiver_salt = random_salt_field();
     let mut remainder_salt = random_salt_field();
+    let max_attempts = options.max_attempts;
+    let mut attempts = 0u32;
 
-    let prepared = loop {
+    let mut prepared = loop {
         let pack = pack_spend_inputs(SpendInputs {
             sender_pkx_be: input.signer.pk_x_bytes(),
             sender_pky_be: input.signer.pk_y_bytes(),
@@ -222,79 +564,75 @@ pub fn prove_spend(req: SpendRequest<'_>) -> anyhow::Result<crate::types::SpendT
         if let Some(check_fn) = ensure_unique
             && check_fn(&[pack.receiver_commit, pack.remainder_commit])?
         {
+            attempts = attempts.saturating_add(1);
+            if attempts >= max_attempts {
+                return Err(CircuitError::UniquenessRetryExhausted.into());
+            }
             receiver_salt = random_salt_field();
             remainder_salt = random_salt_field();
             continue;
         }
         break pack;
     };
+    prepared.salt_attempts = attempts.saturating_add(1);
 
-    let signature = signer.sign_prehash(prepared.msg32);
-    let mut private_inputs = prepared.abi_inputs;
-    private_inputs.insert(
+    prepared.signature = signer.sign_prehash(prepared.msg32);
+    prepared.abi_inputs.insert(
         "input.schnorr.sig64".to_string(),
-        signature.iter().map(|b| fe_from_u8(*b)).collect(),
+        prepared.signature.iter().map(|b| fe_from_u8(*b)).collect(),
     );
+    Ok(prepared)
+}
 
-    let proof = prover::prove_with_all_inputs(SPEND_CIRCUIT, &private_inputs)?;
-    if verify_proof {
+/// Build the Noir ABI for a merge, generate the proof, and return a rich result.
+///
+/// The flow mirrors `prove_spend`, but with two inputs and a single output:
+/// [`prepare_merge`] builds the signed ABI map and output commitment, and a
+/// `MergeTx` is returned once Barretenberg produces the proof.
+pub fn prove_merge(req: MergeRequest<'_>) -> anyhow::Result<MergeTx> {
+    let circuit = req.circuit_name.unwrap_or(MERGE_CIRCUIT);
+    ensure_circuit_loaded(circuit)?;
+    let prepared = prepare_merge(&req)?;
+
+    let proof = prover::prove_with_all_inputs(circuit, &prepared.abi_inputs)?;
+    if req.verify_proof {
         anyhow::ensure!(
-            prover::verify(SPEND_CIRCUIT, &proof)?,
-            "generated spend proof failed verification"
+            prover::verify(circuit, &proof)?,
+            "generated merge proof failed verification"
         );
     }
+    let proof = prover::wrap_proof(&proof, prover::PROOF_FORMAT_VERSION);
 
-    let receiver_utxo = Utxo {
-        assets: array_init::array_init(|idx| Asset {
-            token: receiver_tokens[idx],
-            amount: receiver_amounts[idx],
-        }),
-        recipient_pk_x: Field::from_bytes(recipient_pk_x),
-        salt: receiver_salt,
-    };
-    let remainder_utxo = Utxo {
-        assets: array_init::array_init(|idx| Asset {
-            token: remainder_tokens[idx],
-            amount: remainder_amounts[idx],
-        }),
-        recipient_pk_x: Field::from_bytes(sender_pkx),
-        salt: remainder_salt,
-    };
-
-    Ok(crate::types::SpendTx {
-        input,
-        outputs: TransactionOutput::Spend {
-            receiver: receiver_utxo,
-            remainder: remainder_utxo,
+    Ok(MergeTx {
+        inputs: req.inputs,
+        outputs: TransactionOutput::Merge {
+            utxo: prepared.out_utxo,
         },
-        expected_out_commits: [prepared.receiver_commit, prepared.remainder_commit],
+        expected_out_commit: MergeOutputCommit(prepared.out_commit),
         proof,
-        transfer_token,
-        transfer_amount,
-        fee_amount,
-        signature,
+        signature: prepared.signature,
         msg32: prepared.msg32,
         digest: prepared.digest,
     })
 }
 
-/// Build the Noir ABI for a merge, generate the proof, and return a rich result.
+/// Validate a merge request and build everything the prover needs.
 ///
-/// The flow mirrors `prove_spend`, but with two inputs and a single output. We
-/// derive the output commitment, fill `input.*` entries for both inputs and the
-/// result, and return a `MergeTx` once Barretenberg produces the proof.
+/// The merge counterpart of [`prepare_spend`]: the returned `abi_inputs`
+/// already carry the signature and can be proved directly.
 #[allow(clippy::indexing_slicing)]
-pub fn prove_merge(req: MergeRequest<'_>) -> anyhow::Result<MergeTx> {
-    ensure_circuit_loaded(MERGE_CIRCUIT)?;
+pub fn prepare_merge(req: &MergeRequest<'_>) -> anyhow::Result<MergePrepared> {
     let MergeRequest {
         signer,
-        inputs,
+        ref inputs,
         out_tokens,
         out_amounts,
         out_salt,
+        circuit_name: _,
         ensure_unique,
-        verify_proof,
-    } = req;
+        options,
+        verify_proof: _,
+    } = *req;
 
     let (sender_pkx, sender_pky) = signer.public_key_xy();
 
@@ -318,8 +656,10 @@ pub fn prove_merge(req: MergeRequest<'_>) -> anyhow::Result<MergeTx> {
     );
 
     let mut output_salt = out_salt.unwrap_or_else(random_salt_field);
+    let max_attempts = options.max_attempts;
+    let mut attempts = 0u32;
 
-    let prepared = loop {
+    let mut prepared = loop {
         let pack = pack_merge_inputs(MergeInputs {
             sender_pkx_be: inputs[0].signer.pk_x_bytes(),
             sender_pky_be: inputs[0].signer.pk_y_bytes(),
@@ -336,12 +676,208 @@ pub fn prove_merge(req: MergeRequest<'_>) -> anyhow::Result<MergeTx> {
         if let Some(check_fn) = ensure_unique
             && check_fn(&[pack.out_commit])?
         {
+            attempts = attempts.saturating_add(1);
+            if attempts >= max_attempts {
+                return Err(CircuitError::UniquenessRetryExhausted.into());
+            }
             output_salt = random_salt_field();
             continue;
         }
         break pack;
     };
 
+    prepared.signature = signer.sign_prehash(prepared.msg32);
+    prepared.abi_inputs.insert(
+        "input.schnorr.sig64".to_string(),
+        prepared.signature.iter().map(|b| fe_from_u8(*b)).collect(),
+    );
+    Ok(prepared)
+}
+
+/// Merge three UTXOs by chaining two `prove_merge` calls.
+///
+/// Inputs 0 and 1 are merged into an intermediate UTXO owned by the signer,
+/// which is then merged with input 2 into the requested output. The circuit
+/// requires slot-aligned tokens, so the intermediate simply adds the first two
+/// inputs slot by slot.
+pub fn prove_merge3(req: Merge3Request<'_>) -> anyhow::Result<Merge3Tx> {
+    let Merge3Request {
+        signer,
+        inputs,
+        out_tokens,
+        out_amounts,
+        out_salt,
+        ensure_unique,
+        options,
+        verify_proof,
+    } = req;
+    let [in0, in1, in2] = inputs;
+
+    let (mid_tokens, mid_amounts) = compute_merge_output(&in0.utxo, &in1.utxo)?;
+
+    let first_merge = prove_merge(MergeRequest {
+        signer,
+        inputs: [in0, in1],
+        out_tokens: mid_tokens,
+        out_amounts: mid_amounts,
+        out_salt: None,
+        circuit_name: None,
+        ensure_unique,
+        options,
+        verify_proof,
+    })?;
+    let TransactionOutput::Merge { utxo: intermediate } = &first_merge.outputs else {
+        anyhow::bail!("merge tx outputs must be merge variant");
+    };
+    let intermediate = MergeInput::new(intermediate.clone(), in2.signer);
+
+    let second_merge = prove_merge(MergeRequest {
+        signer,
+        inputs: [intermediate, in2],
+        out_tokens,
+        out_amounts,
+        out_salt,
+        circuit_name: None,
+        ensure_unique,
+        options,
+        verify_proof,
+    })?;
+
+    Ok(Merge3Tx {
+        first_merge,
+        second_merge,
+    })
+}
+
+/// One output of a split transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UtxoOutput {
+    /// Token identifiers, slot-aligned with the consumed input.
+    pub tokens: [Field; 4],
+    /// Amounts aligned with `tokens`.
+    pub amounts: [Field; 4],
+    /// Receiver public key x-coordinate.
+    pub recipient_pk_x: [u8; 32],
+}
+
+/// High-level input for a split proof (one input, three outputs).
+///
+/// This targets the prototype `utxo_split` circuit, which is not embedded yet;
+/// `prove_split` validates and packs the request but fails at proving time
+/// until the artifacts are registered in the catalog.
+pub struct SplitRequest<'a> {
+    /// Schnorr keypair that authorises the transaction.
+    pub signer: &'a Keypair,
+    /// Input payload for the consumed UTXO.
+    pub input: SpendInput,
+    /// The three outputs created by the split.
+    pub outputs: [UtxoOutput; 3],
+    /// Amount to pay as fee (deducted from slot 0).
+    pub fee_amount: Field,
+    /// Run `verify` after proving; useful during tests and debugging.
+    pub verify_proof: bool,
+}
+
+/// Result of a split proof.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitTx {
+    /// Input payload consumed by the split proof.
+    pub input: SpendInput,
+    /// Outputs reconstructed from the request.
+    pub outputs: [Utxo; 3],
+    /// Commitments expected by the circuit, in output order.
+    pub expected_out_commits: [Field; 3],
+    /// Barretenberg proof behind a format version tag; see
+    /// [`prover::wrap_proof`](crate::prover::wrap_proof).
+    pub proof: Vec<u8>,
+    /// Fee paid in slot 0.
+    pub fee_amount: Field,
+    /// Schnorr signature produced by the signer.
+    pub signature: [u8; 64],
+    /// Canonical 32-byte message hashed inside the circuit.
+    pub msg32: [u8; 32],
+    /// Poseidon2 digest corresponding to `msg32` (full field element form).
+    pub digest: Field,
+}
+
+/// Build the Noir ABI for a three-way split, generate the proof, and return it.
+///
+/// Every slot must balance: the outputs either carry the input token or stay
+/// empty, and their amounts sum to the input amount (minus the fee in slot 0).
+/// Validation runs before the circuit is looked up so callers get balance
+/// errors even while `utxo_split` is not registered.
+#[allow(clippy::indexing_slicing, clippy::arithmetic_side_effects)]
+pub fn prove_split(req: SplitRequest<'_>) -> anyhow::Result<SplitTx> {
+    let SplitRequest {
+        signer,
+        input,
+        outputs,
+        fee_amount,
+        verify_proof,
+    } = req;
+
+    let (sender_pkx, sender_pky) = signer.public_key_xy();
+    anyhow::ensure!(
+        sender_pkx == input.signer.pk_x_bytes() && sender_pky == input.signer.pk_y_bytes(),
+        "signer keypair does not match split input public key",
+    );
+    anyhow::ensure!(
+        input.utxo.recipient_pk_x == input.signer.pk_x_field(),
+        "split input utxo recipient key does not match signer key",
+    );
+
+    let zero = Field::from(0u128);
+    for slot in 0..MAX_ASSETS {
+        let in_asset = input.utxo.assets[slot];
+        let available = if slot == 0 {
+            anyhow::ensure!(
+                in_asset.amount >= fee_amount,
+                "insufficient funds to pay fee from slot 0"
+            );
+            in_asset.amount - fee_amount
+        } else {
+            in_asset.amount
+        };
+        // Subtract as we go so no partial sum can wrap around the modulus.
+        let mut remaining = available;
+        for (idx, out) in outputs.iter().enumerate() {
+            anyhow::ensure!(
+                out.tokens[slot] == in_asset.token || out.amounts[slot] == zero,
+                "split output {idx} slot {slot} carries a token not held by the input"
+            );
+            anyhow::ensure!(
+                out.amounts[slot] <= remaining,
+                "split outputs exceed the input in slot {slot}"
+            );
+            remaining -= out.amounts[slot];
+        }
+        anyhow::ensure!(
+            remaining == zero,
+            "split outputs do not balance the input in slot {slot}"
+        );
+    }
+
+    let prepared = pack_split_inputs(SplitInputs {
+        sender_pkx_be: sender_pkx,
+        sender_pky_be: sender_pky,
+        in_tokens: array_init::array_init(|idx| input.utxo.assets[idx].token),
+        in_amounts: array_init::array_init(|idx| input.utxo.assets[idx].amount),
+        in_salt: input.utxo.salt,
+        fee_amount,
+        outputs: array_init::array_init(|idx| {
+            let out = &outputs[idx];
+            Utxo {
+                assets: array_init::array_init(|slot| Asset {
+                    token: out.tokens[slot],
+                    amount: out.amounts[slot],
+                }),
+                recipient_pk_x: Field::from_bytes(out.recipient_pk_x),
+                salt: random_salt_field(),
+            }
+        }),
+    });
+
+    ensure_circuit_loaded(SPLIT_CIRCUIT)?;
     let signature = signer.sign_prehash(prepared.msg32);
     let mut private_inputs = prepared.abi_inputs;
     private_inputs.insert(
@@ -349,28 +885,21 @@ pub fn prove_merge(req: MergeRequest<'_>) -> anyhow::Result<MergeTx> {
         signature.iter().map(|b| fe_from_u8(*b)).collect(),
     );
 
-    let proof = prover::prove_with_all_inputs(MERGE_CIRCUIT, &private_inputs)?;
+    let proof = prover::prove_with_all_inputs(SPLIT_CIRCUIT, &private_inputs)?;
     if verify_proof {
         anyhow::ensure!(
-            prover::verify(MERGE_CIRCUIT, &proof)?,
-            "generated merge proof failed verification"
+            prover::verify(SPLIT_CIRCUIT, &proof)?,
+            "generated split proof failed verification"
         );
     }
+    let proof = prover::wrap_proof(&proof, prover::PROOF_FORMAT_VERSION);
 
-    let merged_utxo = Utxo {
-        assets: array_init::array_init(|idx| Asset {
-            token: out_tokens[idx],
-            amount: out_amounts[idx],
-        }),
-        recipient_pk_x: Field::from_bytes(sender_pkx),
-        salt: output_salt,
-    };
-
-    Ok(MergeTx {
-        inputs,
-        outputs: TransactionOutput::Merge { utxo: merged_utxo },
-        expected_out_commit: prepared.out_commit,
+    Ok(SplitTx {
+        input,
+        outputs: prepared.outputs,
+        expected_out_commits: prepared.out_commits,
         proof,
+        fee_amount,
         signature,
         msg32: prepared.msg32,
         digest: prepared.digest,
@@ -396,18 +925,29 @@ struct SpendInputs {
     remainder_salt: Field,
 }
 
-/// Packed spend inputs alongside the derived commitments/digest.
-struct SpendPrepared {
+/// Packed spend inputs alongside the derived outputs, commitments, and digest.
+///
+/// Returned by [`prepare_spend`] for inspection before (or instead of) proving.
+#[derive(Clone, Debug)]
+pub struct SpendPrepared {
     /// Noir-style ABI map (`input.*` keys) ready for `prove_with_all_inputs`.
-    abi_inputs: HashMap<String, Vec<FE>>,
+    pub abi_inputs: HashMap<String, Vec<FE>>,
+    /// Receiver output UTXO.
+    pub receiver: Utxo,
+    /// Remainder (change) output UTXO.
+    pub remainder: Utxo,
     /// Expected receiver commitment (circuits expose this publicly).
-    receiver_commit: Field,
+    pub receiver_commit: Field,
     /// Expected remainder commitment (circuits expose this publicly).
-    remainder_commit: Field,
+    pub remainder_commit: Field,
     /// Full Poseidon2 digest representing the transaction pre-hash.
-    digest: Field,
+    pub digest: Field,
     /// Digest truncated to 32 bytes (what Schnorr signs).
-    msg32: [u8; 32],
+    pub msg32: [u8; 32],
+    /// Signature over `msg32`, also stored under `input.schnorr.sig64`.
+    pub signature: [u8; 64],
+    /// Salts tried, counting the one `ensure_unique` accepted.
+    pub salt_attempts: u32,
 }
 
 /// Serialise the spend inputs into Noir ABI order and compute commitments.
@@ -524,10 +1064,14 @@ fn pack_spend_inputs(inputs: SpendInputs) -> SpendPrepared {
 
     SpendPrepared {
         abi_inputs: map,
+        receiver,
+        remainder,
         receiver_commit,
         remainder_commit,
         digest,
         msg32,
+        signature: [0u8; 64],
+        salt_attempts: 0,
     }
 }
 
@@ -546,16 +1090,23 @@ struct MergeInputs {
     out_salt: Field,
 }
 
-/// Packed merge inputs alongside the derived commitment/digest.
-struct MergePrepared {
+/// Packed merge inputs alongside the derived output, commitment, and digest.
+///
+/// Returned by [`prepare_merge`] for inspection before (or instead of) proving.
+#[derive(Clone, Debug)]
+pub struct MergePrepared {
     /// Noir-style ABI map (`input.*` keys) ready for `prove_with_all_inputs`.
-    abi_inputs: HashMap<String, Vec<FE>>,
+    pub abi_inputs: HashMap<String, Vec<FE>>,
+    /// Merged output UTXO.
+    pub out_utxo: Utxo,
     /// Expected output commitment (circuits expose this publicly).
-    out_commit: Field,
+    pub out_commit: Field,
     /// Full Poseidon2 digest representing the transaction pre-hash.
-    digest: Field,
+    pub digest: Field,
     /// Digest truncated to 32 bytes (what Schnorr signs).
-    msg32: [u8; 32],
+    pub msg32: [u8; 32],
+    /// Signature over `msg32`, also stored under `input.schnorr.sig64`.
+    pub signature: [u8; 64],
 }
 
 /// Serialise the merge inputs into Noir ABI order and compute commitments.
@@ -649,9 +1200,115 @@ fn pack_merge_inputs(inputs: MergeInputs) -> MergePrepared {
 
     MergePrepared {
         abi_inputs: map,
+        out_utxo,
         out_commit,
         digest,
         msg32,
+        signature: [0u8; 64],
+    }
+}
+
+/// Internal representation of the prototype Noir `SplitInput` struct.
+struct SplitInputs {
+    sender_pkx_be: [u8; 32],
+    sender_pky_be: [u8; 32],
+    in_tokens: [Field; 4],
+    in_amounts: [Field; 4],
+    in_salt: Field,
+    fee_amount: Field,
+    outputs: [Utxo; 3],
+}
+
+/// Packed split inputs alongside the derived commitments/digest.
+struct SplitPrepared {
+    /// Noir-style ABI map (`input.*` keys) ready for `prove_with_all_inputs`.
+    abi_inputs: HashMap<String, Vec<FE>>,
+    /// Output UTXOs including the sampled salts.
+    outputs: [Utxo; 3],
+    /// Expected output commitments, in output order.
+    out_commits: [Field; 3],
+    /// Full Poseidon2 digest representing the transaction pre-hash.
+    digest: Field,
+    /// Digest truncated to 32 bytes (what Schnorr signs).
+    msg32: [u8; 32],
+}
+
+/// Serialise the split inputs into Noir ABI order and compute commitments.
+///
+/// Follows `pack_spend_inputs`; the outputs live under `input.out0` through
+/// `input.out2` and the digest uses tag 3.
+#[allow(clippy::indexing_slicing)]
+fn pack_split_inputs(inputs: SplitInputs) -> SplitPrepared {
+    let out_commits: [Field; 3] = array_init::array_init(|idx| inputs.outputs[idx].commitment());
+
+    let digest = hash_fields(&[
+        Field::from(3u128),
+        Field::from_bytes(inputs.sender_pkx_be),
+        inputs.fee_amount,
+        out_commits[0],
+        out_commits[1],
+        out_commits[2],
+    ]);
+    let msg32 = digest.to_bytes();
+
+    let mut map: HashMap<String, Vec<FE>> = HashMap::new();
+    map.insert(
+        "input.schnorr.pk_x".into(),
+        vec![fe_from_field_bytes(&inputs.sender_pkx_be)],
+    );
+    map.insert(
+        "input.schnorr.pk_y".into(),
+        vec![fe_from_field_bytes(&inputs.sender_pky_be)],
+    );
+    map.insert(
+        "input.schnorr.msg32".into(),
+        msg32.iter().map(|b| fe_from_u8(*b)).collect(),
+    );
+    map.insert(
+        "input.in0.assets_tokens".into(),
+        inputs.in_tokens.iter().map(fe_from_field).collect(),
+    );
+    map.insert(
+        "input.in0.assets_amounts".into(),
+        inputs.in_amounts.iter().map(fe_from_field).collect(),
+    );
+    map.insert(
+        "input.in0.recipient_pk_x".into(),
+        vec![fe_from_field_bytes(&inputs.sender_pkx_be)],
+    );
+    map.insert(
+        "input.in0.salt".into(),
+        vec![fe_from_field(&inputs.in_salt)],
+    );
+    map.insert("input.fee".into(), vec![fe_from_field(&inputs.fee_amount)]);
+    for (idx, out) in inputs.outputs.iter().enumerate() {
+        map.insert(
+            format!("input.out{idx}.assets_tokens"),
+            out.assets.iter().map(|a| fe_from_field(&a.token)).collect(),
+        );
+        map.insert(
+            format!("input.out{idx}.assets_amounts"),
+            out.assets
+                .iter()
+                .map(|a| fe_from_field(&a.amount))
+                .collect(),
+        );
+        map.insert(
+            format!("input.out{idx}.recipient_pk_x"),
+            vec![fe_from_field(&out.recipient_pk_x)],
+        );
+        map.insert(
+            format!("input.out{idx}.salt"),
+            vec![fe_from_field(&out.salt)],
+        );
+    }
+
+    SplitPrepared {
+        abi_inputs: map,
+        outputs: inputs.outputs,
+        out_commits,
+        digest,
+        msg32,
     }
 }
 
@@ -5,13 +5,28 @@
 //! allows the rest of the crate to talk about transactions without referencing
 //! Noir-specific concepts directly.
 
+use std::io::{Read, Write};
+
+use binprot::{BinProtRead, BinProtWrite};
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::batch::{BindingLeaf, CandidateWithRecord, LeafRecord};
+use crate::binprot_io::{
+    invalid_data, read_array, read_bytes, read_option, read_tag, read_u32, write_bytes,
+    write_option, write_tag, write_u32,
+};
 use crate::bn254::Field;
-use crate::poseidon2::{hash_merge_leaf, hash_spend_leaf, hash10};
+use crate::error::ValidationError;
+use crate::grumpkin;
+use crate::hex_fmt;
+use crate::keys::Keypair;
+use crate::note_enc::EncryptedNote;
+use crate::poseidon2::{hash_merge_leaf, hash_n, hash_spend_leaf};
 
 /// Fixed number of asset slots enforced by the Noir circuits.
 pub const MAX_ASSETS: usize = 4;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Asset {
     /// Token identifier committed inside the circuit (BN254 field).
     pub token: Field,
@@ -29,7 +44,7 @@ impl Asset {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct Utxo {
     /// Fixed-width asset vector (four slots, matching the Noir circuit).
     pub assets: [Asset; MAX_ASSETS],
@@ -40,9 +55,69 @@ pub struct Utxo {
 }
 
 impl Utxo {
+    /// UTXO holding a single asset in slot 0, with the other slots empty.
+    pub fn with_single_asset(
+        recipient_pk_x: Field,
+        token: Field,
+        amount: Field,
+        salt: Field,
+    ) -> Self {
+        Self {
+            assets: [
+                Asset { token, amount },
+                Asset::empty(),
+                Asset::empty(),
+                Asset::empty(),
+            ],
+            recipient_pk_x,
+            salt,
+        }
+    }
+
+    /// UTXO holding up to [`MAX_ASSETS`] assets, padded with empty slots.
+    pub fn with_assets_and_salt(
+        assets: &[Asset],
+        recipient_pk_x: Field,
+        salt: Field,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            assets.len() <= MAX_ASSETS,
+            "a UTXO holds at most {MAX_ASSETS} assets, got {}",
+            assets.len()
+        );
+        Ok(Self {
+            assets: std::array::from_fn(|i| assets.get(i).copied().unwrap_or_else(Asset::empty)),
+            recipient_pk_x,
+            salt,
+        })
+    }
+
+    /// Whether two non-empty slots hold the same token.
+    ///
+    /// The circuits require distinct tokens per slot; zero-token slots are
+    /// padding and may repeat.
+    pub fn has_duplicate_tokens(&self) -> bool {
+        self.first_duplicate_token().is_some()
+    }
+
+    /// Lowest pair of slots `(a, b)`, `a < b`, holding the same non-zero token.
+    fn first_duplicate_token(&self) -> Option<(usize, usize)> {
+        self.assets.iter().enumerate().find_map(|(a, first)| {
+            if first.token == Field::zero() {
+                return None;
+            }
+            self.assets
+                .iter()
+                .enumerate()
+                .skip(a.saturating_add(1))
+                .find(|(_, second)| second.token == first.token)
+                .map(|(b, _)| (a, b))
+        })
+    }
+
     /// Compute the Poseidon2 commitment used by the circuits and Merkle tree.
     pub fn commitment(&self) -> Field {
-        hash10([
+        hash_n([
             self.recipient_pk_x,
             self.assets[0].token,
             self.assets[0].amount,
@@ -57,7 +132,40 @@ impl Utxo {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+/// Output token and amount arrays for merging `in0` with `in1`.
+///
+/// The merge circuit requires slot-aligned tokens, so every slot must hold the
+/// same token in both inputs; the amounts are added slot by slot in BN254.
+#[allow(clippy::arithmetic_side_effects)]
+pub fn compute_merge_output(
+    in0: &Utxo,
+    in1: &Utxo,
+) -> anyhow::Result<([Field; MAX_ASSETS], [Field; MAX_ASSETS])> {
+    let mut tokens = [Field::zero(); MAX_ASSETS];
+    let mut amounts = [Field::zero(); MAX_ASSETS];
+    let outputs = tokens.iter_mut().zip(amounts.iter_mut());
+    for (slot, ((a, b), (token, amount))) in
+        in0.assets.iter().zip(&in1.assets).zip(outputs).enumerate()
+    {
+        anyhow::ensure!(
+            a.token == b.token,
+            "merge inputs hold different tokens in slot {slot}: {} and {}",
+            a.token,
+            b.token
+        );
+        *token = a.token;
+        *amount = a.amount + b.amount;
+    }
+    Ok((tokens, amounts))
+}
+
+/// The default is the all-zero key, a placeholder that is not on Grumpkin:
+/// [`SchnorrPublicKey::try_new`] rejects it and no signature verifies under it.
+///
+/// Equality is constant-time; see [`SchnorrPublicKey::ct_eq`]. Deserializing
+/// goes through [`SchnorrPublicKey::try_new`], like the binprot codec.
+#[derive(Clone, Copy, Default, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(try_from = "SchnorrPublicKeyRepr")]
 pub struct SchnorrPublicKey {
     /// X-coordinate of the public key encoded as big-endian bytes.
     pk_x: [u8; 32],
@@ -65,12 +173,62 @@ pub struct SchnorrPublicKey {
     pk_y: [u8; 32],
 }
 
+/// Unchecked wire form of [`SchnorrPublicKey`] for serde.
+#[derive(serde::Deserialize)]
+struct SchnorrPublicKeyRepr {
+    pk_x: [u8; 32],
+    pk_y: [u8; 32],
+}
+
+impl TryFrom<SchnorrPublicKeyRepr> for SchnorrPublicKey {
+    type Error = anyhow::Error;
+
+    fn try_from(repr: SchnorrPublicKeyRepr) -> anyhow::Result<Self> {
+        Self::try_new(repr.pk_x, repr.pk_y)
+    }
+}
+
 impl SchnorrPublicKey {
     /// Construct a Schnorr public key from its affine coordinates.
     pub fn new(pk_x: [u8; 32], pk_y: [u8; 32]) -> Self {
         Self { pk_x, pk_y }
     }
 
+    /// Construct a Schnorr public key, rejecting points that are not on Grumpkin.
+    pub fn try_new(pk_x: [u8; 32], pk_y: [u8; 32]) -> anyhow::Result<Self> {
+        anyhow::ensure!(
+            grumpkin::is_on_curve(Field::from_bytes(pk_x), Field::from_bytes(pk_y)),
+            "public key ({}, {}) is not on the Grumpkin curve",
+            hex_fmt::prefixed(&pk_x),
+            hex_fmt::prefixed(&pk_y)
+        );
+        Ok(Self::new(pk_x, pk_y))
+    }
+
+    /// Construct a Schnorr public key from field-encoded coordinates.
+    pub fn from_fields(pk_x: Field, pk_y: Field) -> Self {
+        Self::new(pk_x.to_bytes(), pk_y.to_bytes())
+    }
+
+    /// Decode the compact `x || y` encoding produced by `to_bytes_64`.
+    pub fn from_bytes_64(bytes: &[u8; 64]) -> Self {
+        let mut pk_x = [0u8; 32];
+        let mut pk_y = [0u8; 32];
+        let (x, y) = bytes.split_at(32);
+        pk_x.copy_from_slice(x);
+        pk_y.copy_from_slice(y);
+        Self { pk_x, pk_y }
+    }
+
+    /// Concatenate the x- and y-coordinates into a 64-byte encoding.
+    pub fn to_bytes_64(&self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        let (x, y) = out.split_at_mut(32);
+        x.copy_from_slice(&self.pk_x);
+        y.copy_from_slice(&self.pk_y);
+        out
+    }
+
     /// Return the x-coordinate as raw bytes.
     pub fn pk_x_bytes(&self) -> [u8; 32] {
         self.pk_x
@@ -90,6 +248,43 @@ impl SchnorrPublicKey {
     pub fn pk_y_field(&self) -> Field {
         Field::from_bytes(self.pk_y)
     }
+
+    /// Verify a Schnorr(Blake2s) signature over `msg32` against this key.
+    pub fn verify(&self, sig64: [u8; 64], msg32: [u8; 32]) -> bool {
+        Keypair::verify_with_xy(self.pk_x, self.pk_y, msg32, sig64)
+    }
+
+    /// Compare both coordinates in constant time.
+    ///
+    /// Every byte of both keys is inspected regardless of where they first
+    /// differ, so the comparison time does not leak a matching prefix.
+    pub fn ct_eq(&self, other: &Self) -> Choice {
+        self.pk_x.ct_eq(&other.pk_x) & self.pk_y.ct_eq(&other.pk_y)
+    }
+}
+
+impl PartialEq for SchnorrPublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).unwrap_u8() == 1
+    }
+}
+
+// Written out by hand to stay consistent with the hand-written `PartialEq`;
+// it hashes exactly what the derive would.
+impl std::hash::Hash for SchnorrPublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.pk_x.hash(state);
+        self.pk_y.hash(state);
+    }
+}
+
+impl std::fmt::Debug for SchnorrPublicKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchnorrPublicKey")
+            .field("pk_x", &hex_fmt::prefixed(&self.pk_x))
+            .field("pk_y", &hex_fmt::prefixed(&self.pk_y))
+            .finish()
+    }
 }
 
 /// Minimal spend input carried across the public API.
@@ -98,7 +293,10 @@ impl SchnorrPublicKey {
 /// recompute commitments from the raw UTXO data, which keeps the API aligned
 /// with what Noir actually consumes today. Proof callers can reintroduce
 /// Merkle data when the circuits need it again.
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// The default is a placeholder holding the all-zero signer key. It encodes,
+/// but neither binprot nor serde decodes it back, since the key is off-curve.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct SpendInput {
     /// UTXO being consumed by the spend proof.
     pub utxo: Utxo,
@@ -111,6 +309,14 @@ impl SpendInput {
     pub fn new(utxo: Utxo, signer: SchnorrPublicKey) -> Self {
         Self { utxo, signer }
     }
+
+    /// Check the invariants the spend circuit enforces on its input UTXO.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if let Some((slot_a, slot_b)) = self.utxo.first_duplicate_token() {
+            return Err(ValidationError::DuplicateToken { slot_a, slot_b });
+        }
+        Ok(())
+    }
 }
 
 /// Minimal merge input carried across the public API.
@@ -118,7 +324,9 @@ impl SpendInput {
 /// Just like `SpendInput`, this only exposes the data Noir reads today – the
 /// consumed UTXO payload along with the signer key. Merkle commitments can be
 /// layered back on when merge circuits require them.
-#[derive(Clone, Debug, PartialEq, Eq)]
+///
+/// As with [`SpendInput`], the default is an undecodable placeholder.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub struct MergeInput {
     /// UTXO being consumed by the merge proof.
     pub utxo: Utxo,
@@ -144,6 +352,85 @@ pub enum TransactionOutput {
     Merge { utxo: Utxo },
 }
 
+impl TransactionOutput {
+    /// Whether these are the outputs of a spend.
+    pub fn is_spend(&self) -> bool {
+        matches!(self, Self::Spend { .. })
+    }
+
+    /// Whether this is the output of a merge.
+    pub fn is_merge(&self) -> bool {
+        matches!(self, Self::Merge { .. })
+    }
+
+    /// The `(receiver, remainder)` pair of a spend, or `None` for a merge.
+    pub fn as_spend(&self) -> Option<(&Utxo, &Utxo)> {
+        match self {
+            Self::Spend {
+                receiver,
+                remainder,
+            } => Some((receiver, remainder)),
+            Self::Merge { .. } => None,
+        }
+    }
+
+    /// The consolidated UTXO of a merge, or `None` for a spend.
+    pub fn as_merge(&self) -> Option<&Utxo> {
+        match self {
+            Self::Merge { utxo } => Some(utxo),
+            Self::Spend { .. } => None,
+        }
+    }
+}
+
+/// Commitments a spend circuit exposes for its two outputs.
+///
+/// Named fields replace the old `[Field; 2]` so the receiver and remainder
+/// cannot be swapped by index.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SpendOutputCommits {
+    /// Commitment of the receiver output.
+    pub receiver: Field,
+    /// Commitment of the remainder (change) output.
+    pub remainder: Field,
+}
+
+impl SpendOutputCommits {
+    /// Commitments in tree insertion order: receiver, then remainder.
+    pub fn to_array(self) -> [Field; 2] {
+        [self.receiver, self.remainder]
+    }
+}
+
+impl From<SpendOutputCommits> for [Field; 2] {
+    fn from(commits: SpendOutputCommits) -> Self {
+        commits.to_array()
+    }
+}
+
+/// Commitment a merge circuit exposes for its consolidated output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MergeOutputCommit(pub Field);
+
+impl MergeOutputCommit {
+    /// The wrapped commitment.
+    pub fn field(self) -> Field {
+        self.0
+    }
+}
+
+impl From<Field> for MergeOutputCommit {
+    fn from(commit: Field) -> Self {
+        Self(commit)
+    }
+}
+
+impl From<MergeOutputCommit> for Field {
+    fn from(commit: MergeOutputCommit) -> Self {
+        commit.0
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SpendTx {
     /// Input payload consumed by the spend proof.
@@ -151,8 +438,9 @@ pub struct SpendTx {
     /// Outputs reconstructed from the private inputs.
     pub outputs: TransactionOutput,
     /// Commitments expected by the circuit (receiver and remainder).
-    pub expected_out_commits: [Field; 2],
-    /// Barretenberg proof bytes.
+    pub expected_out_commits: SpendOutputCommits,
+    /// Barretenberg proof behind a format version tag; see
+    /// [`prover::wrap_proof`](crate::prover::wrap_proof).
     pub proof: Vec<u8>,
     /// Token being transferred.
     pub transfer_token: Field,
@@ -166,10 +454,20 @@ pub struct SpendTx {
     pub msg32: [u8; 32],
     /// Poseidon2 digest corresponding to `msg32` (full field element form).
     pub digest: Field,
+    /// Receiver output encrypted to the recipient, when requested.
+    pub receiver_note: Option<EncryptedNote>,
+    /// Remainder output encrypted to the signer's viewing key, when requested.
+    pub remainder_note: Option<EncryptedNote>,
+    /// Salts `prove_spend` tried before `ensure_unique` accepted one (1 when
+    /// the first salt was kept); 0 when the request did not record attempts.
+    pub salt_attempts: u32,
 }
 
 impl SpendTx {
-    /// Recompute the leaf hash enforced by the circuit for Merkle trees/batches.
+    /// Leaf hash enforced by the circuit for Merkle trees/batches.
+    ///
+    /// Recomputed on every call, since every input field is public and a memo
+    /// could go stale; callers hashing a block should keep the results.
     pub fn leaf_hash(&self) -> Field {
         match &self.outputs {
             TransactionOutput::Spend {
@@ -177,8 +475,8 @@ impl SpendTx {
                 remainder: _,
             } => hash_spend_leaf(
                 self.input.utxo.commitment(),
-                self.expected_out_commits[0],
-                self.expected_out_commits[1],
+                self.expected_out_commits.receiver,
+                self.expected_out_commits.remainder,
                 self.transfer_token,
                 self.transfer_amount,
                 self.fee_amount,
@@ -188,6 +486,70 @@ impl SpendTx {
             }
         }
     }
+
+    /// Receiver and remainder commitments to insert into the UTXO tree.
+    pub fn output_commitments(&self) -> [Field; 2] {
+        self.expected_out_commits.to_array()
+    }
+
+    /// Leaf record matching this transaction, ready for block validation.
+    pub fn to_leaf_record(&self) -> LeafRecord {
+        LeafRecord::from_spend_tx(self)
+    }
+
+    /// Wrap the transaction as a block candidate declaring its own leaf hash.
+    pub fn to_candidate(
+        &self,
+        leaf_id: Vec<u8>,
+        arrival_time_ns: u64,
+        publisher_id: [u8; 32],
+    ) -> CandidateWithRecord {
+        CandidateWithRecord {
+            leaf_id,
+            arrival_time_ns,
+            publisher_id,
+            record: self.to_leaf_record(),
+            declared_leaf_hash: self.leaf_hash(),
+        }
+    }
+
+    /// Wrap the remainder (change) output as an input for a follow-up merge.
+    ///
+    /// The remainder is owned by the spend signer, so its key authorises the
+    /// merge as well.
+    pub fn remainder_as_merge_input(&self) -> anyhow::Result<MergeInput> {
+        let TransactionOutput::Spend { remainder, .. } = &self.outputs else {
+            anyhow::bail!("spend tx outputs must be spend variant");
+        };
+        anyhow::ensure!(
+            remainder.recipient_pk_x == self.input.signer.pk_x_field(),
+            "remainder output is not owned by the spend signer"
+        );
+        Ok(MergeInput::new(remainder.clone(), self.input.signer))
+    }
+
+    /// Serialise the transaction, proof included, with binprot.
+    pub fn to_binprot(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        binprot::BinProtWrite::binprot_write(self, &mut out)
+            .expect("writing binprot to a Vec cannot fail");
+        out
+    }
+
+    /// Parse a transaction written by [`to_binprot`](Self::to_binprot).
+    ///
+    /// Trailing bytes after the transaction are an error.
+    pub fn from_binprot(bytes: &[u8]) -> anyhow::Result<Self> {
+        let mut rest = bytes;
+        let tx = <Self as binprot::BinProtRead>::binprot_read(&mut rest)
+            .map_err(|err| anyhow::anyhow!("decoding SpendTx from binprot: {err:?}"))?;
+        anyhow::ensure!(
+            rest.is_empty(),
+            "{} trailing bytes after binprot SpendTx",
+            rest.len()
+        );
+        Ok(tx)
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -197,8 +559,9 @@ pub struct MergeTx {
     /// Output reconstructed from the private inputs.
     pub outputs: TransactionOutput,
     /// Commitment expected by the merge circuit.
-    pub expected_out_commit: Field,
-    /// Barretenberg proof bytes.
+    pub expected_out_commit: MergeOutputCommit,
+    /// Barretenberg proof behind a format version tag; see
+    /// [`prover::wrap_proof`](crate::prover::wrap_proof).
     pub proof: Vec<u8>,
     /// Schnorr signature produced by the signer.
     pub signature: [u8; 64],
@@ -209,19 +572,64 @@ pub struct MergeTx {
 }
 
 impl MergeTx {
-    /// Recompute the leaf hash enforced by the circuit for Merkle trees/batches.
+    /// Leaf hash enforced by the circuit for Merkle trees/batches.
+    ///
+    /// Recomputed on every call, since every input field is public and a memo
+    /// could go stale; callers hashing a block should keep the results.
     pub fn leaf_hash(&self) -> Field {
         match &self.outputs {
             TransactionOutput::Merge { utxo: _ } => hash_merge_leaf(
                 self.inputs[0].utxo.commitment(),
                 self.inputs[1].utxo.commitment(),
-                self.expected_out_commit,
+                self.expected_out_commit.field(),
             ),
             TransactionOutput::Spend { .. } => {
                 unreachable!("merge tx outputs must be merge variant")
             }
         }
     }
+
+    /// Merged output commitment to insert into the UTXO tree.
+    pub fn output_commitments(&self) -> [Field; 1] {
+        [self.expected_out_commit.field()]
+    }
+
+    /// Leaf record matching this transaction, ready for block validation.
+    pub fn to_leaf_record(&self) -> LeafRecord {
+        LeafRecord::from_merge_tx(self)
+    }
+
+    /// Wrap the transaction as a block candidate declaring its own leaf hash.
+    pub fn to_candidate(
+        &self,
+        leaf_id: Vec<u8>,
+        arrival_time_ns: u64,
+        publisher_id: [u8; 32],
+    ) -> CandidateWithRecord {
+        CandidateWithRecord {
+            leaf_id,
+            arrival_time_ns,
+            publisher_id,
+            record: self.to_leaf_record(),
+            declared_leaf_hash: self.leaf_hash(),
+        }
+    }
+}
+
+/// Two chained merge proofs that together consolidate three UTXOs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Merge3Tx {
+    /// Merge of inputs 0 and 1 into an intermediate UTXO.
+    pub first_merge: MergeTx,
+    /// Merge of the intermediate UTXO with input 2.
+    pub second_merge: MergeTx,
+}
+
+impl Merge3Tx {
+    /// Leaf hashes of both inner merges, in proving order.
+    pub fn leaf_hashes(&self) -> [Field; 2] {
+        [self.first_merge.leaf_hash(), self.second_merge.leaf_hash()]
+    }
 }
 
 // The outer wrapper mirrors the historic API and keeps transaction structs on
@@ -234,3 +642,315 @@ pub enum UtxoTransaction {
     /// Merge transaction wrapper.
     Merge(MergeTx),
 }
+
+impl UtxoTransaction {
+    /// Wrap a spend transaction.
+    pub fn from_spend(tx: SpendTx) -> Self {
+        Self::Spend(tx)
+    }
+
+    /// Wrap a merge transaction.
+    pub fn from_merge(tx: MergeTx) -> Self {
+        Self::Merge(tx)
+    }
+
+    /// Leaf hash of the wrapped transaction.
+    pub fn leaf_hash(&self) -> Field {
+        match self {
+            Self::Spend(tx) => tx.leaf_hash(),
+            Self::Merge(tx) => tx.leaf_hash(),
+        }
+    }
+
+    /// Build the binding leaf for the wrapped transaction.
+    pub fn to_binding_leaf(&self, leaf_id: Vec<u8>) -> BindingLeaf {
+        match self {
+            Self::Spend(tx) => BindingLeaf::from_spend(leaf_id, tx),
+            Self::Merge(tx) => BindingLeaf::from_merge(leaf_id, tx),
+        }
+    }
+}
+
+/// Decimal for values that fit in `u128` (amounts, token ids), hex otherwise.
+fn scalar_summary(value: Field) -> String {
+    let (high, low) = value.as_ref().split_at(16);
+    let mut low_bytes = [0u8; 16];
+    low_bytes.copy_from_slice(low);
+    if high.iter().all(|byte| *byte == 0) {
+        u128::from_be_bytes(low_bytes).to_string()
+    } else {
+        value.to_hex()
+    }
+}
+
+impl std::fmt::Display for SpendTx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SpendTx {{ from: {}, transfer: {} of token {}, fee: {}, proof: {}B }}",
+            hex_fmt::short(&self.input.signer.pk_x_bytes()),
+            scalar_summary(self.transfer_amount),
+            scalar_summary(self.transfer_token),
+            scalar_summary(self.fee_amount),
+            self.proof.len()
+        )
+    }
+}
+
+impl std::fmt::Display for MergeTx {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [first, _] = &self.inputs;
+        write!(
+            f,
+            "MergeTx {{ from: {}, inputs: {}, out: {}, proof: {}B }}",
+            hex_fmt::short(&first.signer.pk_x_bytes()),
+            self.inputs.len(),
+            hex_fmt::short(&self.expected_out_commit.field().to_bytes()),
+            self.proof.len()
+        )
+    }
+}
+
+impl From<SpendTx> for UtxoTransaction {
+    fn from(tx: SpendTx) -> Self {
+        Self::Spend(tx)
+    }
+}
+
+impl From<MergeTx> for UtxoTransaction {
+    fn from(tx: MergeTx) -> Self {
+        Self::Merge(tx)
+    }
+}
+
+impl TryFrom<UtxoTransaction> for SpendTx {
+    type Error = anyhow::Error;
+
+    fn try_from(tx: UtxoTransaction) -> anyhow::Result<Self> {
+        match tx {
+            UtxoTransaction::Spend(tx) => Ok(tx),
+            UtxoTransaction::Merge(_) => anyhow::bail!("expected a spend transaction, got a merge"),
+        }
+    }
+}
+
+impl TryFrom<UtxoTransaction> for MergeTx {
+    type Error = anyhow::Error;
+
+    fn try_from(tx: UtxoTransaction) -> anyhow::Result<Self> {
+        match tx {
+            UtxoTransaction::Merge(tx) => Ok(tx),
+            UtxoTransaction::Spend(_) => anyhow::bail!("expected a merge transaction, got a spend"),
+        }
+    }
+}
+
+// Hand-written binprot codecs. Fields are written in declaration order; byte
+// arrays and proofs go through length-prefixed byte vectors like `Field`.
+
+impl BinProtWrite for Asset {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        self.token.binprot_write(w)?;
+        self.amount.binprot_write(w)
+    }
+}
+
+impl BinProtRead for Asset {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        Ok(Self {
+            token: Field::binprot_read(r)?,
+            amount: Field::binprot_read(r)?,
+        })
+    }
+}
+
+impl BinProtWrite for Utxo {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        for asset in &self.assets {
+            asset.binprot_write(w)?;
+        }
+        self.recipient_pk_x.binprot_write(w)?;
+        self.salt.binprot_write(w)
+    }
+}
+
+impl BinProtRead for Utxo {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        let mut assets = [Asset::empty(); MAX_ASSETS];
+        for asset in &mut assets {
+            *asset = Asset::binprot_read(r)?;
+        }
+        Ok(Self {
+            assets,
+            recipient_pk_x: Field::binprot_read(r)?,
+            salt: Field::binprot_read(r)?,
+        })
+    }
+}
+
+impl BinProtWrite for SchnorrPublicKey {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        write_bytes(w, &self.pk_x)?;
+        write_bytes(w, &self.pk_y)
+    }
+}
+
+impl BinProtRead for SchnorrPublicKey {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        let pk_x = read_array(r)?;
+        let pk_y = read_array(r)?;
+        Self::try_new(pk_x, pk_y).map_err(|err| invalid_data(err.to_string()))
+    }
+}
+
+impl BinProtWrite for SpendInput {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        self.utxo.binprot_write(w)?;
+        self.signer.binprot_write(w)
+    }
+}
+
+impl BinProtRead for SpendInput {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        Ok(Self {
+            utxo: Utxo::binprot_read(r)?,
+            signer: SchnorrPublicKey::binprot_read(r)?,
+        })
+    }
+}
+
+impl BinProtWrite for MergeInput {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        self.utxo.binprot_write(w)?;
+        self.signer.binprot_write(w)
+    }
+}
+
+impl BinProtRead for MergeInput {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        Ok(Self {
+            utxo: Utxo::binprot_read(r)?,
+            signer: SchnorrPublicKey::binprot_read(r)?,
+        })
+    }
+}
+
+impl BinProtWrite for TransactionOutput {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        match self {
+            Self::Spend {
+                receiver,
+                remainder,
+            } => {
+                write_tag(w, 0)?;
+                receiver.binprot_write(w)?;
+                remainder.binprot_write(w)
+            }
+            Self::Merge { utxo } => {
+                write_tag(w, 1)?;
+                utxo.binprot_write(w)
+            }
+        }
+    }
+}
+
+impl BinProtRead for TransactionOutput {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        match read_tag(r)? {
+            0 => Ok(Self::Spend {
+                receiver: Utxo::binprot_read(r)?,
+                remainder: Utxo::binprot_read(r)?,
+            }),
+            1 => Ok(Self::Merge {
+                utxo: Utxo::binprot_read(r)?,
+            }),
+            tag => Err(invalid_data(format!(
+                "invalid TransactionOutput variant {tag}"
+            ))),
+        }
+    }
+}
+
+impl BinProtWrite for SpendTx {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        self.input.binprot_write(w)?;
+        self.outputs.binprot_write(w)?;
+        self.expected_out_commits.receiver.binprot_write(w)?;
+        self.expected_out_commits.remainder.binprot_write(w)?;
+        write_bytes(w, &self.proof)?;
+        self.transfer_token.binprot_write(w)?;
+        self.transfer_amount.binprot_write(w)?;
+        self.fee_amount.binprot_write(w)?;
+        write_bytes(w, &self.signature)?;
+        write_bytes(w, &self.msg32)?;
+        self.digest.binprot_write(w)?;
+        write_option(w, self.receiver_note.as_ref())?;
+        write_option(w, self.remainder_note.as_ref())?;
+        write_u32(w, self.salt_attempts)
+    }
+}
+
+impl BinProtRead for SpendTx {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        let input = SpendInput::binprot_read(r)?;
+        let outputs = TransactionOutput::binprot_read(r)?;
+        if !matches!(outputs, TransactionOutput::Spend { .. }) {
+            return Err(invalid_data(
+                "SpendTx outputs must be the Spend variant".to_string(),
+            ));
+        }
+        Ok(Self {
+            input,
+            outputs,
+            expected_out_commits: SpendOutputCommits {
+                receiver: Field::binprot_read(r)?,
+                remainder: Field::binprot_read(r)?,
+            },
+            proof: read_bytes(r)?,
+            transfer_token: Field::binprot_read(r)?,
+            transfer_amount: Field::binprot_read(r)?,
+            fee_amount: Field::binprot_read(r)?,
+            signature: read_array(r)?,
+            msg32: read_array(r)?,
+            digest: Field::binprot_read(r)?,
+            receiver_note: read_option(r)?,
+            remainder_note: read_option(r)?,
+            salt_attempts: read_u32(r)?,
+        })
+    }
+}
+
+impl BinProtWrite for MergeTx {
+    fn binprot_write<W: Write>(&self, w: &mut W) -> std::io::Result<()> {
+        for input in &self.inputs {
+            input.binprot_write(w)?;
+        }
+        self.outputs.binprot_write(w)?;
+        self.expected_out_commit.field().binprot_write(w)?;
+        write_bytes(w, &self.proof)?;
+        write_bytes(w, &self.signature)?;
+        write_bytes(w, &self.msg32)?;
+        self.digest.binprot_write(w)
+    }
+}
+
+impl BinProtRead for MergeTx {
+    fn binprot_read<R: Read + ?Sized>(r: &mut R) -> Result<Self, binprot::Error> {
+        let inputs = [MergeInput::binprot_read(r)?, MergeInput::binprot_read(r)?];
+        let outputs = TransactionOutput::binprot_read(r)?;
+        if !matches!(outputs, TransactionOutput::Merge { .. }) {
+            return Err(invalid_data(
+                "MergeTx outputs must be the Merge variant".to_string(),
+            ));
+        }
+        Ok(Self {
+            inputs,
+            outputs,
+            expected_out_commit: MergeOutputCommit(Field::binprot_read(r)?),
+            proof: read_bytes(r)?,
+            signature: read_array(r)?,
+            msg32: read_array(r)?,
+            digest: Field::binprot_read(r)?,
+        })
+    }
+}
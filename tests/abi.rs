@@ -0,0 +1,254 @@
+//! ABI inspection helpers against the embedded circuit ABIs.
+
+mod common;
+
+use usernode_circuits::artifacts;
+use usernode_circuits::catalog::{Abi, AbiType};
+use usernode_circuits::prover::encode_spend_privates;
+
+fn embedded_abi(name: &str) -> Abi {
+    let embed = artifacts::embedded()
+        .iter()
+        .find(|c| c.name == name)
+        .expect("embedded circuit");
+    serde_json::from_str(embed.abi_json).expect("parse ABI")
+}
+
+#[test]
+fn spend_abi_counts() {
+    let abi = embedded_abi("utxo_spend");
+    assert_eq!(abi.parameter_count("private"), 1);
+    assert_eq!(abi.parameter_count("public"), 1);
+    // pk_x/pk_y (2) + sig64 (64) + msg32 (32) + three 10-field UTXOs + transfer (3).
+    assert_eq!(abi.total_private_field_count(), 131);
+}
+
+#[test]
+fn leaf_count_matches_the_encoded_spend_privates() {
+    let abi = embedded_abi("utxo_spend");
+    let private_leaves: usize = abi
+        .parameters
+        .iter()
+        .filter(|p| p.visibility == "private")
+        .map(|p| p.abi_type.leaf_count())
+        .sum();
+    let encoded = encode_spend_privates(&common::sample_spend_enc());
+    assert_eq!(private_leaves, encoded.len());
+
+    assert_eq!(AbiType::Boolean.leaf_count(), 1);
+    assert!(matches!(
+        abi.find_type("input.schnorr.sig64")
+            .map(AbiType::leaf_count),
+        Some(64)
+    ));
+}
+
+#[test]
+fn spend_abi_lookup_by_path() {
+    let abi = embedded_abi("utxo_spend");
+    let param = abi
+        .find_parameter("input.transfer.token")
+        .expect("nested field resolves to its parameter");
+    assert_eq!(param.name, "input");
+    assert_eq!(param.visibility, "private");
+
+    assert!(matches!(
+        abi.find_type("input.transfer.token"),
+        Some(AbiType::Field)
+    ));
+    assert!(matches!(
+        abi.find_type("input.schnorr.sig64"),
+        Some(AbiType::Array { length: 64, .. })
+    ));
+    assert!(abi.find_parameter("input.transfer.missing").is_none());
+    assert!(abi.find_parameter("inputs").is_none());
+}
+
+#[test]
+fn spend_abi_param_paths() {
+    let abi = embedded_abi("utxo_spend");
+    let paths = abi.param_paths();
+    assert_eq!(paths.len(), 20);
+    assert_eq!(
+        paths.first().map(String::as_str),
+        Some("input.schnorr.pk_x")
+    );
+    assert!(paths.contains(&"input.transfer.fee".to_string()));
+    assert_eq!(paths.last().map(String::as_str), Some("public_inputs"));
+}
+
+const ARRAY_OF_STRUCTS_ABI: &str = r#"{
+  "parameters": [
+    {
+      "name": "input",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          { "name": "root", "type": { "kind": "field" } },
+          {
+            "name": "paths",
+            "type": {
+              "kind": "array",
+              "length": 2,
+              "type": {
+                "kind": "struct",
+                "fields": [
+                  { "name": "hash", "type": { "kind": "field" } },
+                  { "name": "index", "type": { "kind": "integer", "sign": "unsigned", "width": 32 } }
+                ]
+              }
+            }
+          }
+        ]
+      },
+      "visibility": "private"
+    }
+  ],
+  "return_type": null
+}"#;
+
+#[test]
+fn arrays_of_structs_flatten_with_index_suffix() {
+    use std::collections::HashMap;
+    use usernode_circuits::CircuitFieldElement as FE;
+    use usernode_circuits::prover::encode_abi_inputs;
+
+    let abi: Abi = serde_json::from_str(ARRAY_OF_STRUCTS_ABI).expect("parse ABI");
+    assert_eq!(
+        abi.param_paths(),
+        vec![
+            "input.root",
+            "input.paths[0].hash",
+            "input.paths[0].index",
+            "input.paths[1].hash",
+            "input.paths[1].index",
+        ]
+    );
+    assert_eq!(abi.total_private_field_count(), 5);
+
+    let fe = |v: u128| vec![FE::from(v)];
+    let mut inputs: HashMap<String, Vec<FE>> = HashMap::new();
+    inputs.insert("input.root".into(), fe(1));
+    inputs.insert("input.paths[0].hash".into(), fe(2));
+    inputs.insert("input.paths[0].index".into(), fe(3));
+    inputs.insert("input.paths[1].hash".into(), fe(4));
+    inputs.insert("input.paths[1].index".into(), fe(5));
+
+    let witness = encode_abi_inputs(&abi, &inputs).expect("encode inputs");
+    assert_eq!(witness, (1..=5u128).map(FE::from).collect::<Vec<_>>());
+
+    inputs.remove("input.paths[1].index");
+    let err = encode_abi_inputs(&abi, &inputs).expect_err("missing element must fail");
+    assert!(err.to_string().contains("input.paths[1].index"));
+}
+
+#[test]
+fn validate_inputs_lists_missing_and_wrong_length_keys() {
+    use std::collections::HashMap;
+    use usernode_circuits::CircuitFieldElement as FE;
+
+    let abi: Abi = serde_json::from_str(ARRAY_OF_STRUCTS_ABI).expect("parse ABI");
+    let fe = |v: u128| vec![FE::from(v)];
+    let mut inputs: HashMap<String, Vec<FE>> = HashMap::new();
+    inputs.insert("input.root".into(), fe(1));
+    inputs.insert("input.paths[0].hash".into(), fe(2));
+    inputs.insert("input.paths[0].index".into(), fe(3));
+    inputs.insert("input.paths[1].hash".into(), fe(4));
+    inputs.insert("input.paths[1].index".into(), fe(5));
+    assert_eq!(abi.validate_inputs(&inputs), Ok(()));
+
+    inputs.remove("input.paths[0].hash");
+    inputs.insert("input.root".into(), vec![FE::from(1u128), FE::from(2u128)]);
+    assert_eq!(
+        abi.validate_inputs(&inputs),
+        Err(vec![
+            "input.root".to_string(),
+            "input.paths[0].hash".to_string()
+        ])
+    );
+}
+
+#[test]
+fn validate_inputs_checks_scalar_array_lengths() {
+    use std::collections::HashMap;
+    use usernode_circuits::CircuitFieldElement as FE;
+
+    let abi = embedded_abi("utxo_spend");
+    let err = abi
+        .validate_inputs(&HashMap::new())
+        .expect_err("empty map misses everything");
+    assert_eq!(err.len(), 19, "every private leaf is reported");
+    assert!(err.contains(&"input.schnorr.sig64".to_string()));
+
+    let mut inputs: HashMap<String, Vec<FE>> = HashMap::new();
+    inputs.insert("input.schnorr.sig64".into(), vec![FE::from(0u128); 63]);
+    let err = abi.validate_inputs(&inputs).expect_err("short signature");
+    assert!(err.contains(&"input.schnorr.sig64".to_string()));
+}
+
+const TUPLE_ABI: &str = r#"{
+  "parameters": [
+    {
+      "name": "input",
+      "type": {
+        "kind": "struct",
+        "fields": [
+          {
+            "name": "pair",
+            "type": {
+              "kind": "tuple",
+              "fields": [
+                { "kind": "field" },
+                { "kind": "array", "length": 2, "type": { "kind": "field" } }
+              ]
+            }
+          },
+          { "name": "flag", "type": { "kind": "boolean" } }
+        ]
+      },
+      "visibility": "private"
+    }
+  ],
+  "return_type": null
+}"#;
+
+#[test]
+fn tuples_parse_and_flatten_by_position() {
+    use std::collections::HashMap;
+    use usernode_circuits::CircuitFieldElement as FE;
+    use usernode_circuits::prover::encode_abi_inputs;
+
+    let abi: Abi = serde_json::from_str(TUPLE_ABI).expect("parse tuple ABI");
+    let Some(AbiType::Tuple { elements }) = abi.find_type("input.pair") else {
+        panic!("input.pair should parse as a tuple");
+    };
+    assert!(matches!(
+        elements.as_slice(),
+        [AbiType::Field, AbiType::Array { length: 2, .. }]
+    ));
+    assert!(matches!(
+        abi.find_type("input.pair.1"),
+        Some(AbiType::Array { .. })
+    ));
+    assert_eq!(
+        abi.param_paths(),
+        vec!["input.pair.0", "input.pair.1", "input.flag"]
+    );
+    assert_eq!(abi.total_private_field_count(), 4);
+
+    let mut inputs: HashMap<String, Vec<FE>> = HashMap::new();
+    inputs.insert("input.pair.0".into(), vec![FE::from(1u128)]);
+    inputs.insert(
+        "input.pair.1".into(),
+        vec![FE::from(2u128), FE::from(3u128)],
+    );
+    inputs.insert("input.flag".into(), vec![FE::from(1u128)]);
+    assert_eq!(abi.validate_inputs(&inputs), Ok(()));
+    let witness = encode_abi_inputs(&abi, &inputs).expect("encode tuple inputs");
+    assert_eq!(witness, [1u128, 2, 3, 1].map(FE::from).to_vec());
+
+    // Tuples are written back with Noir's `fields` key.
+    let json = serde_json::to_value(&abi).expect("serialize tuple ABI");
+    let expected: serde_json::Value = serde_json::from_str(TUPLE_ABI).expect("tuple ABI json");
+    assert_eq!(json, expected);
+}
@@ -3,12 +3,82 @@
 //! We build a tiny set of dummy leaves, run them through `plan_block`, and
 //! compare the derived manifest hash/root with the expected Poseidon2 results.
 
-use usernode_circuits::batch::{BindingLeaf, canonical_root_even, plan_block};
+use std::collections::HashSet;
+
+use usernode_circuits::batch::{
+    BindingBlock, BindingLeaf, CandidateLeaf, CandidateWithRecord, LeafRecord, apply_block_outputs,
+    canonical_root_even, compute_new_root, plan_block, plan_block_from_candidates,
+    split_candidates_for_block, validate_and_plan_block,
+};
 use usernode_circuits::bn254::Field;
-use usernode_circuits::poseidon2::{hash_fields, hash_manifest};
+use usernode_circuits::merkle::IncrementalMerkleTree;
+use usernode_circuits::poseidon2::{MANIFEST_V1, h2, hash_fields, hash_manifest_v1};
+use usernode_circuits::types::{
+    Asset, MergeInput, MergeOutputCommit, MergeTx, SchnorrPublicKey, SpendInput,
+    SpendOutputCommits, SpendTx, TransactionOutput, Utxo, UtxoTransaction,
+};
+
+fn note(amount: u128, salt: u128) -> Utxo {
+    Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(amount),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from(11u128),
+        salt: Field::from(salt),
+    }
+}
+
+fn sample_spend_tx() -> SpendTx {
+    let receiver = note(40, 1);
+    let remainder = note(58, 2);
+    SpendTx {
+        input: SpendInput::new(note(100, 3), SchnorrPublicKey::new([1u8; 32], [2u8; 32])),
+        expected_out_commits: SpendOutputCommits {
+            receiver: receiver.commitment(),
+            remainder: remainder.commitment(),
+        },
+        outputs: TransactionOutput::Spend {
+            receiver,
+            remainder,
+        },
+        proof: Vec::new(),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        signature: [0u8; 64],
+        msg32: [0u8; 32],
+        digest: Field::zero(),
+        receiver_note: None,
+        remainder_note: None,
+        salt_attempts: 1,
+    }
+}
+
+fn sample_merge_tx() -> MergeTx {
+    let signer = SchnorrPublicKey::new([1u8; 32], [2u8; 32]);
+    let out = note(100, 6);
+    MergeTx {
+        inputs: [
+            MergeInput::new(note(60, 4), signer),
+            MergeInput::new(note(40, 5), signer),
+        ],
+        expected_out_commit: MergeOutputCommit(out.commitment()),
+        outputs: TransactionOutput::Merge { utxo: out },
+        proof: Vec::new(),
+        signature: [0u8; 64],
+        msg32: [0u8; 32],
+        digest: Field::zero(),
+    }
+}
 
 #[test]
-fn plan_block_drops_tail_and_hashes() {
+fn split_then_plan_block_hashes_the_even_prefix() {
     let base = Field::from(42u128);
     let leaves: Vec<BindingLeaf> = (0..3)
         .map(|i| BindingLeaf {
@@ -17,18 +87,435 @@ fn plan_block_drops_tail_and_hashes() {
         })
         .collect();
 
-    // Plan the block and confirm the last leaf is deferred (odd length).
-    let block = plan_block(7, Field::from(100u128), leaves.clone());
+    // Trim to an even batch, then plan it; the odd tail is left over.
+    let (batch, overflow) = split_candidates_for_block(leaves.clone(), usize::MAX);
+    assert_eq!(overflow.len(), 1);
+    let block = plan_block(7, Field::from(100u128), batch).expect("even batch");
     assert_eq!(block.block_id, 7);
     assert_eq!(block.leaves.len(), 2);
-    assert!(block.deferred.is_some());
+    assert!(block.deferred.is_none());
 
     let expected_hashes: Vec<Field> = leaves.iter().take(2).map(|l| l.leaf_hash).collect();
     assert_eq!(
         block.manifest_hash(),
-        hash_manifest(7, Field::from(100u128), &expected_hashes),
+        hash_manifest_v1(7, Field::from(100u128), &expected_hashes),
     );
 
     let root = canonical_root_even(&expected_hashes).expect("even length root");
     assert_eq!(block.canonical_root_even().expect("root"), root);
+
+    // Skipping the split is an error rather than a block without a root.
+    assert!(plan_block(7, Field::from(100u128), leaves).is_err());
+}
+
+#[test]
+fn leaf_records_round_trip_through_json() {
+    let spend = LeafRecord::Spend {
+        in_commit: Field::from(1u128),
+        out_commit0: Field::from(2u128),
+        out_commit1: Field::from(3u128),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+    };
+    let merge = LeafRecord::Merge {
+        in_commit0: Field::from(4u128),
+        in_commit1: Field::from(5u128),
+        out_commit: Field::from(6u128),
+    };
+
+    for record in [spend, merge] {
+        let json = record.to_json();
+        assert!(
+            json.contains("\"0x00000000"),
+            "fields are hex encoded: {json}"
+        );
+        let decoded = LeafRecord::from_json(&json).expect("decode record");
+        assert_eq!(decoded, record);
+        assert_eq!(decoded.recompute_leaf_hash(), record.recompute_leaf_hash());
+    }
+    assert!(LeafRecord::from_json("{\"Merge\":{}}").is_err());
+}
+
+#[test]
+fn candidate_with_record_round_trips_through_json() {
+    let record = LeafRecord::Merge {
+        in_commit0: Field::from(4u128),
+        in_commit1: Field::from(5u128),
+        out_commit: Field::from(6u128),
+    };
+    let candidate = CandidateWithRecord {
+        leaf_id: vec![1, 2, 3],
+        arrival_time_ns: 99,
+        publisher_id: [8u8; 32],
+        declared_leaf_hash: record.recompute_leaf_hash(),
+        record,
+    };
+    let json = serde_json::to_string(&candidate).expect("serialize candidate");
+    let decoded: CandidateWithRecord = serde_json::from_str(&json).expect("decode candidate");
+    assert_eq!(decoded.record, candidate.record);
+    assert_eq!(decoded.declared_leaf_hash, candidate.declared_leaf_hash);
+    assert_eq!(decoded.leaf_id, candidate.leaf_id);
+}
+
+#[test]
+fn leaf_records_from_transactions_match_leaf_hashes() {
+    let spend = sample_spend_tx();
+    let record = LeafRecord::from_spend_tx(&spend);
+    assert!(matches!(record, LeafRecord::Spend { .. }));
+    assert_eq!(record.recompute_leaf_hash(), spend.leaf_hash());
+
+    let merge = sample_merge_tx();
+    let record = LeafRecord::from_merge_tx(&merge);
+    assert!(matches!(record, LeafRecord::Merge { .. }));
+    assert_eq!(record.recompute_leaf_hash(), merge.leaf_hash());
+}
+
+#[test]
+fn utxo_transaction_binding_leaves_match_records() {
+    let spend = sample_spend_tx();
+    let merge = sample_merge_tx();
+    let spend_record = LeafRecord::from_spend_tx(&spend);
+    let merge_record = LeafRecord::from_merge_tx(&merge);
+
+    let direct = BindingLeaf::from_spend(vec![1], &spend);
+    assert_eq!(direct.leaf_hash, spend_record.recompute_leaf_hash());
+
+    let wrapped = UtxoTransaction::from_spend(spend);
+    let leaf = wrapped.to_binding_leaf(vec![1]);
+    assert_eq!(leaf.leaf_id, vec![1]);
+    assert_eq!(leaf.leaf_hash, spend_record.recompute_leaf_hash());
+    assert_eq!(wrapped.leaf_hash(), leaf.leaf_hash);
+
+    let wrapped = UtxoTransaction::from_merge(merge);
+    let leaf = wrapped.to_binding_leaf(vec![2]);
+    assert_eq!(leaf.leaf_hash, merge_record.recompute_leaf_hash());
+    assert_eq!(wrapped.leaf_hash(), leaf.leaf_hash);
+}
+
+#[test]
+fn utxo_transactions_convert_with_into_and_try_from() {
+    let spend = sample_spend_tx();
+    let merge = sample_merge_tx();
+    let txs: Vec<UtxoTransaction> = vec![spend.clone().into(), merge.clone().into()];
+    assert!(matches!(txs.first(), Some(UtxoTransaction::Spend(_))));
+    assert!(matches!(txs.get(1), Some(UtxoTransaction::Merge(_))));
+
+    let mut iter = txs.into_iter();
+    let first = iter.next().expect("spend entry");
+    let second = iter.next().expect("merge entry");
+    assert!(MergeTx::try_from(first.clone()).is_err());
+    assert!(SpendTx::try_from(second.clone()).is_err());
+    assert_eq!(SpendTx::try_from(first).expect("spend variant"), spend);
+    assert_eq!(MergeTx::try_from(second).expect("merge variant"), merge);
+}
+
+#[test]
+#[allow(deprecated)]
+fn manifest_v1_matches_legacy_hash() {
+    use usernode_circuits::poseidon2::hash_manifest;
+
+    let root = Field::from(100u128);
+    let leaves = [Field::from(1u128), Field::from(2u128)];
+    for (block_id, hashes) in [(7u64, leaves.as_slice()), (0, &[])] {
+        assert_eq!(
+            hash_manifest_v1(block_id, root, hashes),
+            hash_manifest(block_id, root, hashes)
+        );
+    }
+
+    // The v1 layout written out by hand: [tag, block_id, root, len, digest].
+    let expected = hash_fields(&[
+        Field::from(MANIFEST_V1),
+        Field::from(7u128),
+        root,
+        Field::from(2u128),
+        hash_fields(&leaves),
+    ]);
+    assert_eq!(hash_manifest_v1(7, root, &leaves), expected);
+}
+
+#[test]
+fn block_from_transaction_convenience_methods() {
+    let spend = sample_spend_tx();
+    let merge = sample_merge_tx();
+    assert_eq!(spend.to_leaf_record(), LeafRecord::from_spend_tx(&spend));
+    assert_eq!(merge.to_leaf_record(), LeafRecord::from_merge_tx(&merge));
+
+    let candidates = vec![
+        spend.to_candidate(b"spend".to_vec(), 10, [1u8; 32]),
+        merge.to_candidate(b"merge".to_vec(), 20, [2u8; 32]),
+    ];
+    let block = validate_and_plan_block(
+        3,
+        Field::from(100u128),
+        candidates,
+        |_| true,
+        &HashSet::new(),
+        &HashSet::new(),
+    );
+
+    assert!(block.deferred.is_none());
+    let hashes: Vec<Field> = block.leaves.iter().map(|l| l.leaf_hash).collect();
+    assert_eq!(hashes, vec![spend.leaf_hash(), merge.leaf_hash()]);
+    assert_eq!(
+        block.manifest_hash(),
+        hash_manifest_v1(3, Field::from(100u128), &hashes)
+    );
+}
+
+#[test]
+fn iter_leaves_visits_block_order() {
+    let leaves: Vec<BindingLeaf> = (0..4u8)
+        .map(|i| BindingLeaf {
+            leaf_id: vec![i],
+            leaf_hash: Field::from(u128::from(i)),
+        })
+        .collect();
+    let block = plan_block(1, Field::zero(), leaves).expect("even leaves");
+    let ids: Vec<Vec<u8>> = block.iter_leaves().map(|l| l.leaf_id.clone()).collect();
+    assert_eq!(ids, vec![vec![0], vec![1], vec![2], vec![3]]);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_iter_leaves_matches_sequential_sum() {
+    use rayon::iter::ParallelIterator;
+
+    let leaves: Vec<BindingLeaf> = (0..8u8)
+        .map(|i| BindingLeaf {
+            leaf_id: vec![i],
+            leaf_hash: hash_fields(&[Field::from(u128::from(i))]),
+        })
+        .collect();
+    let block = plan_block(1, Field::zero(), leaves).expect("even leaves");
+
+    let sequential = block
+        .iter_leaves()
+        .fold(Field::zero(), |acc, l| acc + l.leaf_hash);
+    let parallel = block
+        .par_iter_leaves()
+        .map(|l| l.leaf_hash)
+        .reduce(Field::zero, |a, b| a + b);
+    assert_eq!(sequential, parallel);
+}
+
+#[test]
+fn candidate_sequence_breaks_full_ties() {
+    let candidate = |leaf_id: u8, sequence: u64| CandidateLeaf {
+        leaf_id: vec![leaf_id],
+        leaf_hash: Field::from(5u128),
+        arrival_time_ns: 100,
+        publisher_id: [3u8; 32],
+        sequence,
+    };
+
+    for candidates in [
+        vec![candidate(1, 2), candidate(2, 1)],
+        vec![candidate(2, 1), candidate(1, 2)],
+    ] {
+        let block = plan_block_from_candidates(1, Field::zero(), candidates);
+        let ids: Vec<Vec<u8>> = block.iter_leaves().map(|l| l.leaf_id.clone()).collect();
+        assert_eq!(ids, vec![vec![2], vec![1]]);
+    }
+}
+
+#[test]
+fn validate_rejects_note_spent_in_prior_block() {
+    let spend = sample_spend_tx();
+    let merge = sample_merge_tx();
+    let spent_input = spend.input.utxo.commitment().to_bytes();
+    let candidates = || {
+        vec![
+            spend.to_candidate(b"spend".to_vec(), 10, [1u8; 32]),
+            merge.to_candidate(b"merge".to_vec(), 20, [2u8; 32]),
+        ]
+    };
+
+    let fresh = validate_and_plan_block(
+        4,
+        Field::zero(),
+        candidates(),
+        |_| true,
+        &HashSet::new(),
+        &HashSet::new(),
+    );
+    assert_eq!(fresh.iter_leaves().count(), 2);
+
+    let replay = validate_and_plan_block(
+        4,
+        Field::zero(),
+        candidates(),
+        |_| true,
+        &HashSet::from([spent_input]),
+        &HashSet::new(),
+    );
+    let ids: Vec<&[u8]> = replay
+        .iter_leaves()
+        .chain(replay.deferred.iter())
+        .map(|l| l.leaf_id.as_slice())
+        .collect();
+    assert_eq!(ids, vec![b"merge".as_slice()]);
+}
+
+#[test]
+fn validate_accepts_outputs_produced_in_prior_block() {
+    let spend = sample_spend_tx();
+    let input = spend.input.utxo.commitment();
+    let candidate = spend.to_candidate(b"spend".to_vec(), 10, [1u8; 32]);
+
+    // Not in the accepted tree, but created by an earlier block.
+    let block = validate_and_plan_block(
+        5,
+        Field::zero(),
+        vec![candidate],
+        |_| false,
+        &HashSet::new(),
+        &HashSet::from([input.to_bytes()]),
+    );
+    assert_eq!(block.deferred.map(|l| l.leaf_id), Some(b"spend".to_vec()));
+}
+
+#[test]
+fn compute_new_root_inserts_into_empty_depth_two_tree() {
+    let leaf = Field::from(42u128);
+    let zero = Field::zero();
+    let zero_subtree = h2(zero, zero);
+    let empty = IncrementalMerkleTree::<2>::new();
+    assert_eq!(empty.root(), h2(zero_subtree, zero_subtree));
+
+    let expected = h2(h2(leaf, zero), zero_subtree);
+    assert_eq!(compute_new_root(&empty, &[leaf]).expect("fits"), expected);
+    assert!(empty.is_empty(), "the tree itself is not modified");
+
+    // The next append lands in slot 1, next to the first leaf.
+    let mut tree = empty.clone();
+    tree.append(leaf).expect("slot 0");
+    let next = Field::from(7u128);
+    assert_eq!(
+        compute_new_root(&tree, &[next]).expect("fits"),
+        h2(h2(leaf, next), zero_subtree)
+    );
+
+    let overflow = [Field::from(1u128); 4];
+    assert!(compute_new_root(&tree, &overflow).is_err());
+}
+
+#[test]
+fn apply_block_outputs_collects_commitments_in_block_order() {
+    let spend = sample_spend_tx();
+    let merge = sample_merge_tx();
+    let block = plan_block(
+        4,
+        Field::zero(),
+        vec![
+            BindingLeaf::from_spend(b"spend".to_vec(), &spend),
+            BindingLeaf::from_merge(b"merge".to_vec(), &merge),
+        ],
+    )
+    .expect("even leaves");
+
+    // Records may arrive in any order; outputs follow the block.
+    let records = [merge.to_leaf_record(), spend.to_leaf_record()];
+    let outputs = apply_block_outputs(&block, &records);
+    assert_eq!(outputs.len(), 3);
+
+    let expected: Vec<Field> = spend
+        .output_commitments()
+        .into_iter()
+        .chain(merge.output_commitments())
+        .collect();
+    assert_eq!(outputs, expected);
+}
+
+#[test]
+fn verify_leaf_hashes_reports_mismatched_positions() {
+    let spend = sample_spend_tx();
+    let merge = sample_merge_tx();
+    let block = plan_block(
+        6,
+        Field::zero(),
+        vec![
+            BindingLeaf::from_spend(b"spend".to_vec(), &spend),
+            BindingLeaf::from_merge(b"merge".to_vec(), &merge),
+        ],
+    )
+    .expect("even leaves");
+    let records = vec![spend.to_leaf_record(), merge.to_leaf_record()];
+    assert_eq!(block.verify_leaf_hashes(&records), Ok(()));
+
+    let mut tampered = records.clone();
+    if let Some(LeafRecord::Merge { out_commit, .. }) = tampered.get_mut(1) {
+        *out_commit = Field::from(123u128);
+    }
+    assert_eq!(block.verify_leaf_hashes(&tampered), Err(vec![1]));
+
+    let short = records.get(..1).expect("one record");
+    assert_eq!(block.verify_leaf_hashes(short), Err(vec![0, 1]));
+}
+
+#[test]
+fn planned_blocks_always_have_a_batch_root() {
+    for count in 1..=6u8 {
+        let leaves: Vec<BindingLeaf> = (0..count)
+            .map(|i| BindingLeaf {
+                leaf_id: vec![i],
+                leaf_hash: Field::from(u128::from(i).saturating_add(1)),
+            })
+            .collect();
+        let (batch, _) = split_candidates_for_block(leaves, usize::MAX);
+        let block = plan_block(7, Field::zero(), batch).expect("even batch");
+        if block.leaves.is_empty() {
+            assert!(block.try_batch_root().is_none());
+            assert!(block.batch_root().is_err());
+            continue;
+        }
+        let root = block.try_batch_root().expect("even block has a root");
+        assert_eq!(block.batch_root().expect("batch root"), root);
+    }
+
+    let odd = BindingBlock {
+        block_id: 8,
+        acceptance_root: Field::zero(),
+        leaves: vec![BindingLeaf {
+            leaf_id: vec![0],
+            leaf_hash: Field::one(),
+        }],
+        deferred: None,
+    };
+    assert!(odd.try_batch_root().is_none());
+    assert!(odd.batch_root().is_err());
+}
+
+#[test]
+fn split_candidates_caps_the_batch_and_keeps_the_overflow_in_order() {
+    let leaves: Vec<BindingLeaf> = (0..7u8)
+        .map(|i| BindingLeaf {
+            leaf_id: vec![i],
+            leaf_hash: Field::from(u128::from(i)),
+        })
+        .collect();
+    let ids = |leaves: &[BindingLeaf]| -> Vec<u8> {
+        leaves
+            .iter()
+            .filter_map(|l| l.leaf_id.first().copied())
+            .collect()
+    };
+
+    let (batch, overflow) = split_candidates_for_block(leaves.clone(), 4);
+    assert_eq!(ids(&batch), vec![0, 1, 2, 3]);
+    assert_eq!(ids(&overflow), vec![4, 5, 6]);
+
+    // An odd cap rounds down so the batch stays pair-complete.
+    let (batch, overflow) = split_candidates_for_block(leaves.clone(), 5);
+    assert_eq!(batch.len(), 4);
+    assert!(!overflow.is_empty());
+
+    let (batch, overflow) = split_candidates_for_block(leaves.clone(), 0);
+    assert!(batch.is_empty());
+    assert_eq!(overflow.len(), leaves.len());
+
+    let (batch, overflow) = split_candidates_for_block(leaves.get(..6).expect("six").to_vec(), 8);
+    assert_eq!(batch.len(), 6);
+    assert!(overflow.is_empty());
 }
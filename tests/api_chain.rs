@@ -0,0 +1,83 @@
+//! Chaining checks: spend a note, then merge its change with another note.
+
+mod common;
+
+use common::serial_guard;
+use usernode_circuits::bn254::Field;
+use usernode_circuits::catalog;
+use usernode_circuits::keys::Keypair;
+use usernode_circuits::tx::{MergeRequest, RequestOptions, SpendRequest, prove_merge, prove_spend};
+use usernode_circuits::types::{Asset, MergeInput, SchnorrPublicKey, SpendInput, Utxo};
+
+#[test]
+fn spend_remainder_chains_into_merge() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let signer_pk = SchnorrPublicKey::new(signer_pk_x, signer_pk_y);
+    let note = |amount: u128, salt: u128| Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(amount),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(salt),
+    };
+
+    let spend = prove_spend(SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(note(100, 1111), signer_pk),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    })
+    .expect("spend proof");
+    assert!(usernode_circuits::verify_tx("utxo_spend", &spend.proof).expect("verify spend"));
+
+    let change = spend.remainder_as_merge_input().expect("remainder input");
+    assert_eq!(
+        change.utxo.commitment(),
+        spend.expected_out_commits.remainder
+    );
+
+    let merge = prove_merge(MergeRequest {
+        signer: &signer,
+        inputs: [change, MergeInput::new(note(10, 2222), signer_pk)],
+        out_tokens: [
+            Field::from(7u128),
+            Field::zero(),
+            Field::zero(),
+            Field::zero(),
+        ],
+        out_amounts: [
+            Field::from(68u128),
+            Field::zero(),
+            Field::zero(),
+            Field::zero(),
+        ],
+        out_salt: None,
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: false,
+    })
+    .expect("merge proof");
+    assert!(usernode_circuits::verify_tx("utxo_merge", &merge.proof).expect("verify merge"));
+    catalog::clear();
+}
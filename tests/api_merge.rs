@@ -9,7 +9,9 @@ use common::serial_guard;
 use usernode_circuits::bn254::Field;
 use usernode_circuits::catalog;
 use usernode_circuits::keys::Keypair;
-use usernode_circuits::tx::{MergeRequest, prove_merge};
+use usernode_circuits::tx::{
+    Merge3Request, MergeRequest, RequestOptions, prove_merge, prove_merge3,
+};
 use usernode_circuits::types::{Asset, MergeInput, SchnorrPublicKey, TransactionOutput, Utxo};
 
 #[test]
@@ -18,7 +20,7 @@ fn merge_prove_matches_commitment() {
     catalog::clear();
     usernode_circuits::init_default_circuits().expect("init embedded circuits");
 
-    let signer = Keypair::from_seed([5u8; 32]).expect("derive keypair");
+    let signer = Keypair::test_pair(5);
 
     let utxo_from_input = |amount: u128, salt: u128| Utxo {
         assets: [
@@ -61,7 +63,9 @@ fn merge_prove_matches_commitment() {
         out_tokens,
         out_amounts,
         out_salt: Some(Field::from(1234u128)),
+        circuit_name: None,
         ensure_unique: None,
+        options: RequestOptions::default(),
         verify_proof: true,
     })
     .expect("merge proof generation");
@@ -77,6 +81,102 @@ fn merge_prove_matches_commitment() {
     }
 
     // Validate the generated proof against the embedded verification key.
-    assert!(usernode_circuits::verify("utxo_merge", &tx.proof).expect("verify"));
+    assert!(usernode_circuits::verify_tx("utxo_merge", &tx.proof).expect("verify"));
     catalog::clear();
 }
+
+#[test]
+fn merge3_chains_two_verifiable_merges() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(5);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let signer_pk = SchnorrPublicKey::new(signer_pk_x, signer_pk_y);
+    let input = |amount: u128, salt: u128| {
+        MergeInput::new(
+            Utxo {
+                assets: [
+                    Asset {
+                        token: Field::from(7u128),
+                        amount: Field::from(amount),
+                    },
+                    Asset::empty(),
+                    Asset::empty(),
+                    Asset::empty(),
+                ],
+                recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+                salt: Field::from(salt),
+            },
+            signer_pk,
+        )
+    };
+
+    let tx = prove_merge3(Merge3Request {
+        signer: &signer,
+        inputs: [input(50, 20), input(30, 21), input(20, 22)],
+        out_tokens: [
+            Field::from(7u128),
+            Field::zero(),
+            Field::zero(),
+            Field::zero(),
+        ],
+        out_amounts: [
+            Field::from(100u128),
+            Field::zero(),
+            Field::zero(),
+            Field::zero(),
+        ],
+        out_salt: Some(Field::from(4321u128)),
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: false,
+    })
+    .expect("chained merge proofs");
+
+    assert!(usernode_circuits::verify_tx("utxo_merge", &tx.first_merge.proof).expect("verify"));
+    assert!(usernode_circuits::verify_tx("utxo_merge", &tx.second_merge.proof).expect("verify"));
+    assert_eq!(
+        tx.second_merge.inputs[0].utxo.commitment(),
+        tx.first_merge.expected_out_commit.field()
+    );
+    assert_eq!(
+        tx.leaf_hashes(),
+        [tx.first_merge.leaf_hash(), tx.second_merge.leaf_hash()]
+    );
+    catalog::clear();
+}
+
+#[test]
+fn from_utxos_fills_the_summed_output() {
+    let signer = Keypair::test_pair(5);
+    let owned = |amount: u128, salt: u128| {
+        Utxo::with_single_asset(
+            Field::from_bytes(signer.public_key_xonly()),
+            Field::from(7u128),
+            Field::from(amount),
+            Field::from(salt),
+        )
+    };
+
+    let req = MergeRequest::from_utxos(&signer, owned(60, 10), owned(40, 11)).expect("request");
+    let [out_token, ..] = req.out_tokens;
+    let [out_amount, ..] = req.out_amounts;
+    assert_eq!(out_token, Field::from(7u128));
+    assert_eq!(out_amount, Field::from(100u128));
+    let (pk_x, pk_y) = signer.public_key_xy();
+    assert!(
+        req.inputs
+            .iter()
+            .all(|input| input.signer == SchnorrPublicKey::new(pk_x, pk_y))
+    );
+
+    let other_token = Utxo::with_single_asset(
+        Field::from_bytes(signer.public_key_xonly()),
+        Field::from(8u128),
+        Field::from(1u128),
+        Field::from(12u128),
+    );
+    assert!(MergeRequest::from_utxos(&signer, owned(60, 10), other_token).is_err());
+}
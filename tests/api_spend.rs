@@ -6,12 +6,18 @@
 
 mod common;
 
+use std::cell::Cell;
+
 use common::serial_guard;
 use usernode_circuits::bn254::Field;
 use usernode_circuits::catalog;
 use usernode_circuits::keys::Keypair;
-use usernode_circuits::tx::{SpendRequest, prove_spend};
+use usernode_circuits::poseidon2::hash_n;
+use usernode_circuits::tx::{
+    RequestOptions, SpendRequest, SpendRequestView, prepare_spend, prove_spend,
+};
 use usernode_circuits::types::{Asset, SchnorrPublicKey, SpendInput, TransactionOutput, Utxo};
+use usernode_circuits::{CircuitError, PROOF_FORMAT_VERSION, ValidationError};
 
 #[test]
 fn spend_prove_matches_commitments() {
@@ -19,8 +25,8 @@ fn spend_prove_matches_commitments() {
     catalog::clear();
     usernode_circuits::init_default_circuits().expect("init embedded circuits");
 
-    let signer = Keypair::from_seed([7u8; 32]).expect("derive keypair");
-    let recipient = Keypair::from_seed([9u8; 32]).expect("derive recipient");
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
 
     let input_utxo = Utxo {
         assets: [
@@ -54,8 +60,12 @@ fn spend_prove_matches_commitments() {
         transfer_token,
         transfer_amount,
         fee_amount,
+        circuit_name: None,
         ensure_unique: None,
+        options: RequestOptions::default(),
         verify_proof: true,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
     })
     .expect("spend proof generation");
 
@@ -77,7 +87,563 @@ fn spend_prove_matches_commitments() {
     assert_eq!(tx.fee_amount, fee_amount);
     assert_eq!(tx.input.signer.pk_x_bytes(), signer.public_key_xonly());
     // Finally confirm the proof verifies against the embedded verification key.
-    assert!(usernode_circuits::verify("utxo_spend", &tx.proof).expect("verify"));
+    assert!(usernode_circuits::verify_tx("utxo_spend", &tx.proof).expect("verify"));
+
+    catalog::clear();
+}
+
+#[test]
+fn spend_from_another_slot_pays_the_fee_from_slot_0() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+
+    let fee_token = Field::from(1u128);
+    let transfer_token = Field::from(7u128);
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token: fee_token,
+                amount: Field::from(10u128),
+            },
+            Asset::empty(),
+            Asset {
+                token: transfer_token,
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+    let request = SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(input_utxo, SchnorrPublicKey::new(signer_pk_x, signer_pk_y)),
+        transfer_token,
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: true,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    };
+
+    let tx = prove_spend(request).expect("prove spend");
+    let TransactionOutput::Spend {
+        receiver,
+        remainder,
+    } = &tx.outputs
+    else {
+        panic!("expected spend outputs");
+    };
+    assert_eq!(receiver.assets[2].token, transfer_token);
+    assert_eq!(receiver.assets[2].amount, Field::from(40u128));
+    assert_eq!(remainder.assets[0].amount, Field::from(8u128));
+    assert_eq!(remainder.assets[2].amount, Field::from(60u128));
 
     catalog::clear();
 }
+
+#[test]
+fn ensure_unique_gives_up_after_max_attempts() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+
+    let calls = Cell::new(0u32);
+    let always_taken = |_: &[Field]| -> anyhow::Result<bool> {
+        calls.set(calls.get().saturating_add(1));
+        Ok(true)
+    };
+    let err = prove_spend(SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(input_utxo, SchnorrPublicKey::new(signer_pk_x, signer_pk_y)),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: Some(&always_taken),
+        options: RequestOptions {
+            max_attempts: 100,
+            record_attempts: true,
+        },
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    })
+    .expect_err("uniqueness retries must be bounded");
+
+    assert_eq!(
+        err.downcast_ref::<CircuitError>(),
+        Some(&CircuitError::UniquenessRetryExhausted)
+    );
+    assert_eq!(calls.get(), 100);
+    catalog::clear();
+}
+
+#[test]
+fn salt_attempts_counts_ensure_unique_retries() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+
+    let calls = Cell::new(0u32);
+    let taken_three_times = |_: &[Field]| -> anyhow::Result<bool> {
+        calls.set(calls.get().saturating_add(1));
+        Ok(calls.get() <= 3)
+    };
+    let request = |record_attempts: bool| SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(
+            input_utxo.clone(),
+            SchnorrPublicKey::new(signer_pk_x, signer_pk_y),
+        ),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: Some(&taken_three_times),
+        options: RequestOptions {
+            max_attempts: 10,
+            record_attempts,
+        },
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    };
+
+    let tx = prove_spend(request(true)).expect("spend after retries");
+    assert_eq!(tx.salt_attempts, 4);
+    assert_eq!(calls.get(), 4);
+
+    calls.set(0);
+    let prepared = prepare_spend(&request(false)).expect("prepare after retries");
+    assert_eq!(prepared.salt_attempts, 4);
+
+    catalog::clear();
+}
+
+#[test]
+fn spend_request_view_round_trips_through_json() {
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+    let never_taken = |_: &[Field]| -> anyhow::Result<bool> { Ok(false) };
+    let request = SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(input_utxo, SchnorrPublicKey::new(signer_pk_x, signer_pk_y)),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: Some(&never_taken),
+        options: RequestOptions {
+            max_attempts: 5,
+            record_attempts: false,
+        },
+        verify_proof: true,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    };
+
+    let view = request.to_view();
+    assert_eq!(view.signer, SchnorrPublicKey::new(signer_pk_x, signer_pk_y));
+    assert!(view.has_unique_check);
+
+    let json = serde_json::to_string(&view).expect("serialize view");
+    let decoded: SpendRequestView = serde_json::from_str(&json).expect("deserialize view");
+    assert_eq!(decoded, view);
+
+    // Views logged before `options` existed still parse, with the defaults.
+    let mut legacy: serde_json::Value = serde_json::from_str(&json).expect("json value");
+    if let Some(fields) = legacy.as_object_mut() {
+        fields.remove("options");
+    }
+    let decoded: SpendRequestView = serde_json::from_value(legacy).expect("view without options");
+    assert_eq!(decoded.options, RequestOptions::default());
+}
+
+#[test]
+fn eq_ignoring_closure_skips_the_uniqueness_check() {
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let never_taken = |_: &[Field]| -> anyhow::Result<bool> { Ok(false) };
+    let checked = SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(
+            Utxo::with_single_asset(
+                Field::from_bytes(signer.public_key_xonly()),
+                Field::from(7u128),
+                Field::from(100u128),
+                Field::from(1111u128),
+            ),
+            SchnorrPublicKey::new(signer_pk_x, signer_pk_y),
+        ),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: Some(&never_taken),
+        options: RequestOptions::default(),
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    };
+    let unchecked = SpendRequest {
+        input: checked.input.clone(),
+        ensure_unique: None,
+        ..checked
+    };
+    assert!(checked.has_unique_check());
+    assert!(!unchecked.has_unique_check());
+    assert!(checked.eq_ignoring_closure(&unchecked));
+    assert!(unchecked.eq_ignoring_closure(&checked));
+
+    let larger = SpendRequest {
+        input: checked.input.clone(),
+        transfer_amount: Field::from(41u128),
+        ..checked
+    };
+    assert!(!checked.eq_ignoring_closure(&larger));
+}
+
+#[test]
+fn spend_proves_with_custom_circuit_name() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+    let embed = usernode_circuits::artifacts::embedded()
+        .iter()
+        .find(|e| e.name == "utxo_spend")
+        .expect("embedded utxo_spend");
+    usernode_circuits::init_circuit_from_artifacts(
+        "utxo_spend_test",
+        embed.acir,
+        embed.vk,
+        embed.abi_json,
+    )
+    .expect("register renamed circuit");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+    let tx = prove_spend(SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(input_utxo, SchnorrPublicKey::new(signer_pk_x, signer_pk_y)),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: Some("utxo_spend_test"),
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: true,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    })
+    .expect("prove with renamed circuit");
+    assert!(usernode_circuits::verify_tx("utxo_spend_test", &tx.proof).expect("verify"));
+    catalog::clear();
+}
+
+#[test]
+fn spend_attaches_decryptable_output_notes() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+    let tx = prove_spend(SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(input_utxo, SchnorrPublicKey::new(signer_pk_x, signer_pk_y)),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: Some(recipient.viewing_key().incoming()),
+    })
+    .expect("spend with encrypted notes");
+
+    let TransactionOutput::Spend {
+        receiver,
+        remainder,
+    } = &tx.outputs
+    else {
+        panic!("spend tx must produce spend outputs");
+    };
+    let receiver_note = tx.receiver_note.as_ref().expect("receiver note");
+    let remainder_note = tx.remainder_note.as_ref().expect("remainder note");
+
+    assert_eq!(
+        recipient.viewing_key().try_decrypt(receiver_note).as_ref(),
+        Some(receiver)
+    );
+    // No other viewing key opens it, including the sender's.
+    let outsider = Keypair::test_pair(10);
+    assert_eq!(outsider.viewing_key().try_decrypt(receiver_note), None);
+    assert_eq!(signer.viewing_key().try_decrypt(receiver_note), None);
+    assert_eq!(
+        signer.viewing_key().try_decrypt(remainder_note).as_ref(),
+        Some(remainder)
+    );
+    catalog::clear();
+}
+
+#[test]
+fn prepare_spend_exposes_abi_inputs_for_direct_proving() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+    let request = SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(input_utxo, SchnorrPublicKey::new(signer_pk_x, signer_pk_y)),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    };
+
+    let prepared = prepare_spend(&request).expect("prepare spend");
+    let receiver = &prepared.receiver;
+    assert_eq!(
+        prepared.receiver_commit,
+        hash_n([
+            Field::from_bytes(recipient.public_key_xonly()),
+            receiver.assets[0].token,
+            receiver.assets[0].amount,
+            receiver.assets[1].token,
+            receiver.assets[1].amount,
+            receiver.assets[2].token,
+            receiver.assets[2].amount,
+            receiver.assets[3].token,
+            receiver.assets[3].amount,
+            receiver.salt,
+        ])
+    );
+    assert_eq!(receiver.assets[0].amount, Field::from(40u128));
+    assert!(signer.verify(prepared.msg32, prepared.signature));
+
+    // The packed ABI map is all the low-level prover needs.
+    let proof = usernode_circuits::prove_with_all_inputs("utxo_spend", &prepared.abi_inputs)
+        .expect("prove from prepared inputs");
+    assert!(usernode_circuits::verify("utxo_spend", &proof).expect("verify"));
+    catalog::clear();
+}
+
+#[test]
+fn verify_proof_uses_the_supplied_verifying_key() {
+    let _guard = serial_guard();
+    catalog::clear();
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let spend_vk = usernode_circuits::get_vk_bytes("utxo_spend").expect("spend vk");
+    let merge_vk = usernode_circuits::get_vk_bytes("utxo_merge").expect("merge vk");
+    let request = |verifier_vk_bytes: Option<&[u8]>| SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input: SpendInput::new(
+            Utxo::with_single_asset(
+                Field::from_bytes(signer.public_key_xonly()),
+                Field::from(7u128),
+                Field::from(100u128),
+                Field::from(1111u128),
+            ),
+            SchnorrPublicKey::new(signer_pk_x, signer_pk_y),
+        ),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: true,
+        verifier_vk_bytes,
+        recipient_ivk: None,
+    };
+
+    let tx = prove_spend(request(Some(&spend_vk))).expect("verifies with explicit vk");
+    let bare = usernode_circuits::unwrap_proof_version(&tx.proof, PROOF_FORMAT_VERSION)
+        .expect("versioned spend proof");
+    assert!(usernode_circuits::verify_with_vk_bytes(bare, &spend_vk).expect("verify"));
+    assert_eq!(
+        request(Some(&spend_vk))
+            .to_view()
+            .verifier_vk_len
+            .map(|len| len > 0),
+        Some(true)
+    );
+
+    // A mismatched key is used as given instead of the catalog's spend VK.
+    assert!(prove_spend(request(Some(&merge_vk))).is_err());
+    catalog::clear();
+}
+
+#[test]
+fn duplicate_input_tokens_are_rejected_before_proving() {
+    let _guard = serial_guard();
+    // No circuits registered: a proof attempt would fail with a lookup error.
+    catalog::clear();
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let (signer_pk_x, signer_pk_y) = signer.public_key_xy();
+    let token = Field::from(7u128);
+    let input_utxo = Utxo {
+        assets: [
+            Asset {
+                token,
+                amount: Field::from(60u128),
+            },
+            Asset::empty(),
+            Asset {
+                token,
+                amount: Field::from(40u128),
+            },
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(1111u128),
+    };
+    assert!(input_utxo.has_duplicate_tokens());
+    let input = SpendInput::new(input_utxo, SchnorrPublicKey::new(signer_pk_x, signer_pk_y));
+    let expected = ValidationError::DuplicateToken {
+        slot_a: 0,
+        slot_b: 2,
+    };
+    assert_eq!(input.validate(), Err(expected));
+
+    let err = prove_spend(SpendRequest {
+        signer: &signer,
+        recipient_pk_x: recipient.public_key_xonly(),
+        input,
+        transfer_token: token,
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        circuit_name: None,
+        ensure_unique: None,
+        options: RequestOptions::default(),
+        verify_proof: false,
+        verifier_vk_bytes: None,
+        recipient_ivk: None,
+    })
+    .expect_err("duplicate tokens must be rejected");
+    assert_eq!(err.downcast_ref::<ValidationError>(), Some(&expected));
+}
@@ -0,0 +1,118 @@
+//! Stub checks for the prototype split API.
+//!
+//! The `utxo_split` circuit is not embedded yet, so these tests pin the request
+//! shape and the balance validation that runs before proving.
+
+mod common;
+
+use common::serial_guard;
+use usernode_circuits::bn254::Field;
+use usernode_circuits::catalog;
+use usernode_circuits::keys::Keypair;
+use usernode_circuits::tx::{SplitRequest, UtxoOutput, prove_split};
+use usernode_circuits::types::{Asset, SchnorrPublicKey, SpendInput, Utxo};
+
+fn output(amount: u128, recipient_pk_x: [u8; 32]) -> UtxoOutput {
+    UtxoOutput {
+        tokens: [
+            Field::from(7u128),
+            Field::zero(),
+            Field::zero(),
+            Field::zero(),
+        ],
+        amounts: [
+            Field::from(amount),
+            Field::zero(),
+            Field::zero(),
+            Field::zero(),
+        ],
+        recipient_pk_x,
+    }
+}
+
+fn split_input(signer: &Keypair) -> SpendInput {
+    let (pk_x, pk_y) = signer.public_key_xy();
+    let utxo = Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(100u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(signer.public_key_xonly()),
+        salt: Field::from(4242u128),
+    };
+    SpendInput::new(utxo, SchnorrPublicKey::new(pk_x, pk_y))
+}
+
+#[test]
+fn split_rejects_unbalanced_outputs() {
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let to = recipient.public_key_xonly();
+
+    let err = prove_split(SplitRequest {
+        signer: &signer,
+        input: split_input(&signer),
+        outputs: [output(50, to), output(30, to), output(20, to)],
+        fee_amount: Field::from(2u128),
+        verify_proof: false,
+    })
+    .expect_err("outputs ignore the fee");
+    assert!(err.to_string().contains("exceed the input"), "{err}");
+
+    let err = prove_split(SplitRequest {
+        signer: &signer,
+        input: split_input(&signer),
+        outputs: [output(50, to), output(30, to), output(10, to)],
+        fee_amount: Field::from(2u128),
+        verify_proof: false,
+    })
+    .expect_err("outputs leave value unspent");
+    assert!(err.to_string().contains("do not balance"), "{err}");
+}
+
+#[test]
+#[allow(clippy::arithmetic_side_effects)]
+fn split_rejects_outputs_that_wrap_the_modulus() {
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let to = recipient.public_key_xonly();
+
+    // 50 + (p - 1) + 49 wraps to the 98 available after the fee.
+    let mut wrapping = output(0, to);
+    wrapping.amounts[0] = Field::zero() - Field::from(1u128);
+    let err = prove_split(SplitRequest {
+        signer: &signer,
+        input: split_input(&signer),
+        outputs: [output(50, to), wrapping, output(49, to)],
+        fee_amount: Field::from(2u128),
+        verify_proof: false,
+    })
+    .expect_err("wrapped sum must not balance");
+    assert!(err.to_string().contains("exceed the input"), "{err}");
+}
+
+#[test]
+fn split_balanced_request_waits_for_circuit() {
+    let _guard = serial_guard();
+    catalog::clear();
+
+    let signer = Keypair::test_pair(7);
+    let recipient = Keypair::test_pair(9);
+    let to = recipient.public_key_xonly();
+
+    let err = prove_split(SplitRequest {
+        signer: &signer,
+        input: split_input(&signer),
+        outputs: [output(50, to), output(30, to), output(18, to)],
+        fee_amount: Field::from(2u128),
+        verify_proof: true,
+    })
+    .expect_err("utxo_split is not embedded yet");
+    assert!(err.to_string().contains("utxo_split"), "{err}");
+    catalog::clear();
+}
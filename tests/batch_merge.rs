@@ -11,7 +11,7 @@ use usernode_circuits::poseidon2::h2;
 use usernode_circuits::prover::{
     SchnorrEnc, SpendInputEnc, TransferEnc, UtxoEnc, encode_spend_privates, get_circuit,
     get_key_id, get_vk_bytes_by_id, get_vk_hash_by_id, init_default_circuits, merge_batch_h2_by_id,
-    prove, verify,
+    merge_tree, prove, verify, verify_with_vk_bytes,
 };
 
 #[test]
@@ -407,3 +407,25 @@ fn merge_batch_by_id_regenerates_verifying_keys() {
 
     catalog::clear();
 }
+
+#[test]
+fn merge_tree_folds_four_spend_proofs() {
+    let _lock = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let privates = encode_spend_privates(&common::sample_spend_enc());
+    let key_id = get_key_id("utxo_spend").expect("vk id");
+    let proofs: Vec<Vec<u8>> = (0..4)
+        .map(|_| prove("utxo_spend", &privates).expect("prove utxo_spend"))
+        .collect();
+    let leaves: Vec<(&[u8], [u8; 32])> = proofs.iter().map(|p| (p.as_slice(), key_id)).collect();
+
+    let (root_proof, root_vk_id) = merge_tree(&leaves).expect("merge tree");
+    let root_vk = get_vk_bytes_by_id(root_vk_id).expect("root vk cached");
+    assert!(verify_with_vk_bytes(&root_proof, &root_vk).expect("verify root"));
+
+    let odd = leaves.get(..3).expect("three leaves");
+    assert!(merge_tree(odd).is_err(), "odd leaf count rejected");
+    catalog::clear();
+}
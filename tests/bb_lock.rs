@@ -0,0 +1,42 @@
+//! The Barretenberg lock is usable from outside the crate.
+
+mod common;
+
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use common::serial_guard;
+use usernode_circuits::barretenberg::with_bb_lock;
+use usernode_circuits::catalog;
+use usernode_circuits::keys::Keypair;
+use usernode_circuits::prover;
+
+#[test]
+fn external_lock_serialises_with_internal_proving() {
+    let _guard = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded circuits");
+    let signer = Keypair::test_pair(7);
+    let privates = prover::encode_spend_privates(&common::sample_spend_enc());
+
+    let (started_tx, started_rx) = mpsc::channel();
+    let (done_tx, done_rx) = mpsc::channel();
+    let (sig64, prover_thread) = with_bb_lock(|| {
+        let prover_thread = thread::spawn(move || {
+            started_tx.send(()).expect("signal start");
+            let proof = prover::prove("utxo_spend", &privates).expect("prove");
+            done_tx.send(()).expect("signal done");
+            proof
+        });
+        started_rx.recv().expect("prover thread started");
+        // The prover must wait for us to release the lock.
+        assert!(done_rx.recv_timeout(Duration::from_millis(200)).is_err());
+        (signer.sign_prehash([1u8; 32]), prover_thread)
+    });
+
+    let proof = prover_thread.join().expect("prover thread");
+    assert!(prover::verify("utxo_spend", &proof).expect("verify"));
+    assert!(signer.verify([1u8; 32], sig64));
+    catalog::clear();
+}
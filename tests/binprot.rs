@@ -0,0 +1,155 @@
+//! Binprot round trips for the transaction types.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::keys::Keypair;
+use usernode_circuits::note_enc::EncryptedNote;
+use usernode_circuits::types::{
+    Asset, MergeInput, MergeOutputCommit, MergeTx, SchnorrPublicKey, SpendInput,
+    SpendOutputCommits, SpendTx, TransactionOutput, Utxo,
+};
+
+fn utxo(amount: u128, salt: u128) -> Utxo {
+    Utxo::with_single_asset(
+        Field::from(42u128),
+        Field::from(7u128),
+        Field::from(amount),
+        Field::from(salt),
+    )
+}
+
+fn signer() -> SchnorrPublicKey {
+    let (pk_x, pk_y) = Keypair::test_pair(3).public_key_xy();
+    SchnorrPublicKey::new(pk_x, pk_y)
+}
+
+fn sample_spend() -> SpendTx {
+    let signer = signer();
+    let receiver = utxo(40, 1);
+    let remainder = utxo(58, 2);
+    SpendTx {
+        input: SpendInput::new(utxo(100, 3), signer),
+        expected_out_commits: SpendOutputCommits {
+            receiver: receiver.commitment(),
+            remainder: remainder.commitment(),
+        },
+        outputs: TransactionOutput::Spend {
+            receiver,
+            remainder,
+        },
+        proof: Vec::new(),
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        signature: [9u8; 64],
+        msg32: [4u8; 32],
+        digest: Field::from(5u128),
+        receiver_note: Some(EncryptedNote {
+            ciphertext: vec![1, 2, 3],
+            ephemeral_pk_x: [6u8; 32],
+            ephemeral_pk_y: [7u8; 32],
+        }),
+        remainder_note: None,
+        salt_attempts: 4,
+    }
+}
+
+#[test]
+fn spend_tx_round_trips_through_binprot() {
+    let tx = sample_spend();
+    let bytes = tx.to_binprot();
+    let decoded = SpendTx::from_binprot(&bytes).expect("decode spend");
+    assert_eq!(decoded, tx);
+    assert_eq!(decoded.leaf_hash(), tx.leaf_hash());
+
+    let with_proof = SpendTx {
+        proof: vec![0xcd; 96],
+        ..tx
+    };
+    let decoded = SpendTx::from_binprot(&with_proof.to_binprot()).expect("decode with proof");
+    assert_eq!(decoded.proof, with_proof.proof);
+}
+
+#[test]
+fn malformed_binprot_spend_is_rejected() {
+    let bytes = sample_spend().to_binprot();
+    let (truncated, _) = bytes.split_at(bytes.len().saturating_sub(1));
+    assert!(SpendTx::from_binprot(truncated).is_err());
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(SpendTx::from_binprot(&trailing).is_err());
+
+    let off_curve = SpendTx {
+        input: SpendInput::new(utxo(100, 3), SchnorrPublicKey::new([0xab; 32], [2u8; 32])),
+        ..sample_spend()
+    };
+    assert!(SpendTx::from_binprot(&off_curve.to_binprot()).is_err());
+}
+
+#[test]
+fn tag_swapped_outputs_are_rejected() {
+    use binprot::{BinProtRead, BinProtWrite};
+
+    let signer = signer();
+    let swapped_spend = SpendTx {
+        outputs: TransactionOutput::Merge { utxo: utxo(98, 1) },
+        ..sample_spend()
+    };
+    assert!(SpendTx::from_binprot(&swapped_spend.to_binprot()).is_err());
+
+    let swapped_merge = MergeTx {
+        inputs: [
+            MergeInput::new(utxo(60, 4), signer),
+            MergeInput::new(utxo(40, 5), signer),
+        ],
+        expected_out_commit: MergeOutputCommit(Field::from(1u128)),
+        outputs: TransactionOutput::Spend {
+            receiver: utxo(60, 1),
+            remainder: utxo(40, 2),
+        },
+        proof: Vec::new(),
+        signature: [1u8; 64],
+        msg32: [2u8; 32],
+        digest: Field::from(3u128),
+    };
+    let mut bytes = Vec::new();
+    swapped_merge
+        .binprot_write(&mut bytes)
+        .expect("encode merge");
+    assert!(MergeTx::binprot_read(&mut bytes.as_slice()).is_err());
+}
+
+#[test]
+fn merge_tx_round_trips_through_binprot() {
+    use binprot::{BinProtRead, BinProtWrite};
+
+    let signer = signer();
+    let out = utxo(100, 6);
+    let tx = MergeTx {
+        inputs: [
+            MergeInput::new(utxo(60, 4), signer),
+            MergeInput::new(utxo(40, 5), signer),
+        ],
+        expected_out_commit: MergeOutputCommit(out.commitment()),
+        outputs: TransactionOutput::Merge { utxo: out },
+        proof: Vec::new(),
+        signature: [1u8; 64],
+        msg32: [2u8; 32],
+        digest: Field::from(3u128),
+    };
+    let mut bytes = Vec::new();
+    tx.binprot_write(&mut bytes).expect("encode merge");
+    let decoded = MergeTx::binprot_read(&mut bytes.as_slice()).expect("decode merge");
+    assert_eq!(decoded, tx);
+
+    let asset = Asset {
+        token: Field::from(11u128),
+        amount: Field::from(12u128),
+    };
+    let mut bytes = Vec::new();
+    asset.binprot_write(&mut bytes).expect("encode asset");
+    assert_eq!(
+        Asset::binprot_read(&mut bytes.as_slice()).expect("decode asset"),
+        asset
+    );
+}
@@ -0,0 +1,42 @@
+/* Smoke test for the C exports; built and run by tests/ffi_c.rs. */
+#include <stdio.h>
+#include <string.h>
+
+#include "usernode_circuits.h"
+
+int main(void) {
+    if (usernode_init(NULL) != 0) {
+        fprintf(stderr, "usernode_init failed\n");
+        return 1;
+    }
+
+    uint8_t inputs[64];
+    memset(inputs, 0, sizeof inputs);
+    inputs[31] = 1;
+    inputs[63] = 2;
+    uint8_t out[32];
+    if (usernode_hash_fields(inputs, sizeof inputs, out) != 0) {
+        fprintf(stderr, "usernode_hash_fields failed\n");
+        return 1;
+    }
+    if (usernode_hash_fields(inputs, 31, out) != -1) {
+        fprintf(stderr, "usernode_hash_fields accepted a partial field\n");
+        return 1;
+    }
+    for (size_t i = 0; i < sizeof out; i++) {
+        printf("%02x", out[i]);
+    }
+    printf("\n");
+
+    uint8_t garbage[16] = {0};
+    int rc = usernode_verify("utxo_spend", garbage, sizeof garbage);
+    if (rc == 1) {
+        fprintf(stderr, "garbage proof verified\n");
+        return 1;
+    }
+    if (usernode_verify("no_such_circuit", garbage, sizeof garbage) != -1) {
+        fprintf(stderr, "unknown circuit did not error\n");
+        return 1;
+    }
+    return 0;
+}
@@ -26,3 +26,28 @@ fn embedded_catalog_init_is_thread_safe() {
             .expect("init should succeed");
     }
 }
+
+#[test]
+fn list_circuits_reflects_registration() {
+    let before = usernode_circuits::list_circuits();
+    // Other tests in this binary may already have loaded the embedded set.
+    assert!(before.is_empty() || before.contains(&"utxo_spend".to_string()));
+    if !before.is_empty() {
+        assert!(usernode_circuits::is_circuit_registered("utxo_spend"));
+    }
+
+    usernode_circuits::init_default_circuits().expect("load embedded circuits");
+    let after = usernode_circuits::list_circuits();
+    assert!(after.contains(&"utxo_spend".to_string()));
+    assert!(after.contains(&"utxo_merge".to_string()));
+    assert!(
+        after
+            .windows(2)
+            .all(|pair| matches!(pair, [a, b] if a <= b))
+    );
+    assert!(usernode_circuits::is_circuit_registered("utxo_merge"));
+    assert!(!usernode_circuits::is_circuit_registered("no_such_circuit"));
+
+    usernode_circuits::init_default_circuits().expect("re-init is idempotent");
+    assert_eq!(usernode_circuits::list_circuits(), after);
+}
@@ -0,0 +1,84 @@
+//! Round-trip the catalog through an on-disk artifact directory.
+
+mod common;
+
+use common::serial_guard;
+use usernode_circuits::catalog;
+use usernode_circuits::prover;
+
+#[test]
+fn export_then_import_restores_catalog() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+    let spend = prover::get_circuit("utxo_spend").expect("embedded spend circuit");
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let exported = catalog::export_to_directory(dir.path()).expect("export catalog");
+    assert_eq!(
+        exported,
+        vec!["utxo_merge".to_string(), "utxo_spend".to_string()]
+    );
+    assert!(dir.path().join("utxo_spend.acir").is_file());
+    assert!(dir.path().join("utxo_spend.vk").is_file());
+    assert!(dir.path().join("utxo_spend.abi.json").is_file());
+
+    catalog::clear();
+    let imported = catalog::import_from_directory(dir.path()).expect("import catalog");
+    assert_eq!(imported, exported);
+
+    let restored = prover::get_circuit("utxo_spend").expect("imported spend circuit");
+    assert_eq!(restored.acir, spend.acir);
+    assert_eq!(restored.vk, spend.vk);
+    assert_eq!(restored.key_id, spend.key_id);
+    assert_eq!(
+        restored.abi.parameters.len(),
+        spend.abi.parameters.len(),
+        "ABI should survive the round trip"
+    );
+
+    catalog::clear();
+}
+
+#[test]
+fn export_writes_lazy_entries_without_compiling_them() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_lazy_circuits().expect("register embedded circuits lazily");
+    let mut broken = catalog::peek("utxo_merge").expect("merge registered");
+    broken.name = "broken_lazy".to_string();
+    broken.acir = vec![0xff; 8];
+    catalog::insert(broken);
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    let exported = catalog::export_to_directory(dir.path()).expect("export catalog");
+    assert_eq!(
+        exported,
+        vec![
+            "broken_lazy".to_string(),
+            "utxo_merge".to_string(),
+            "utxo_spend".to_string()
+        ]
+    );
+    let acir = std::fs::read(dir.path().join("broken_lazy.acir")).expect("read exported ACIR");
+    assert_eq!(acir, vec![0xff; 8]);
+    for name in &exported {
+        assert!(!catalog::peek(name).expect("still registered").is_compiled());
+    }
+
+    catalog::clear();
+}
+
+#[test]
+fn import_skips_incomplete_triples() {
+    let _lock = serial_guard();
+    catalog::clear();
+
+    let dir = tempfile::tempdir().expect("tempdir");
+    std::fs::write(dir.path().join("orphan.acir"), [0u8; 4]).expect("write acir");
+    std::fs::write(dir.path().join("orphan.abi.json"), "{}").expect("write abi");
+
+    let imported = catalog::import_from_directory(dir.path()).expect("import catalog");
+    assert!(imported.is_empty(), "acir without vk must be skipped");
+    assert!(catalog::all_loaded().is_empty());
+}
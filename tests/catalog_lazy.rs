@@ -0,0 +1,62 @@
+//! Lazily registered circuits compile on first lookup only.
+
+mod common;
+
+use common::serial_guard;
+use usernode_circuits::catalog;
+use usernode_circuits::prover;
+
+#[test]
+fn first_lookup_compiles_only_that_circuit() {
+    let _guard = serial_guard();
+    catalog::clear();
+
+    prover::init_lazy_circuits().expect("register embedded circuits lazily");
+    let spend = catalog::peek("utxo_spend").expect("spend registered");
+    let merge = catalog::peek("utxo_merge").expect("merge registered");
+    assert!(!spend.is_compiled());
+    assert!(!merge.is_compiled());
+    assert!(prover::is_circuit_registered("utxo_merge"));
+    assert!(!catalog::peek("utxo_merge").expect("merge").is_compiled());
+
+    let spend = prover::get_circuit("utxo_spend").expect("spend compiles on access");
+    assert!(spend.is_compiled());
+    assert!(spend.vk_hash.is_some());
+    assert_eq!(
+        prover::get_circuit("utxo_spend").map(|entry| entry.key_id),
+        Some(spend.key_id),
+        "later lookups reuse the compiled entry"
+    );
+    assert!(!catalog::peek("utxo_merge").expect("merge").is_compiled());
+
+    // Registering again leaves the compiled entry in place.
+    prover::init_lazy_circuits().expect("re-register");
+    assert!(catalog::peek("utxo_spend").expect("spend").is_compiled());
+    catalog::clear();
+}
+
+#[test]
+fn failed_lazy_compilation_is_not_handed_out() {
+    let _guard = serial_guard();
+    catalog::clear();
+
+    prover::init_lazy_circuits().expect("register embedded circuits lazily");
+    let mut broken = catalog::peek("utxo_merge").expect("merge registered");
+    broken.name = "broken_lazy".to_string();
+    broken.acir = vec![0xff; 8];
+    broken.vk = Vec::new();
+    catalog::insert(broken);
+
+    assert!(catalog::get("broken_lazy").is_none());
+    assert!(prover::get_circuit("broken_lazy").is_none());
+    let err = catalog::get_compiled("broken_lazy").expect_err("garbage ACIR");
+    assert!(format!("{err:#}").contains("compile_mega"), "{err:#}");
+    let err = prover::verify("broken_lazy", &[]).expect_err("compile error surfaces");
+    assert!(format!("{err:#}").contains("compile_mega"), "{err:#}");
+    assert!(
+        !catalog::peek("broken_lazy")
+            .expect("still registered")
+            .is_compiled()
+    );
+    catalog::clear();
+}
@@ -1,6 +1,7 @@
 mod common;
 
 use common::serial_guard;
+use sha2::{Digest, Sha256};
 use usernode_circuits::artifacts;
 use usernode_circuits::catalog::{self, CircuitEntry};
 use usernode_circuits::prover;
@@ -22,6 +23,88 @@ fn embedded_catalog_includes_metadata() {
     catalog::clear();
 }
 
+#[test]
+fn embedded_circuits_have_descriptions() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+
+    for embed in artifacts::embedded() {
+        assert!(!embed.description.trim().is_empty(), "{}", embed.name);
+    }
+    let mut described = catalog::get_all_with_descriptions();
+    described.sort();
+    let names: Vec<&str> = described.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["utxo_merge", "utxo_spend"]);
+    for (name, description) in &described {
+        let description = description.as_deref().unwrap_or_default();
+        assert!(!description.is_empty(), "{name} has no description");
+        assert_eq!(description, description.trim_end(), "{name}");
+    }
+
+    // Circuits registered at runtime carry no description.
+    let spend = prover::get_circuit("utxo_spend").expect("embedded spend circuit");
+    prover::init_circuit_from_artifacts(
+        "runtime_spend",
+        &spend.acir,
+        &spend.vk,
+        artifacts::embedded()
+            .iter()
+            .find(|c| c.name == "utxo_spend")
+            .expect("embedded spend circuit")
+            .abi_json,
+    )
+    .expect("register circuit");
+    let runtime = prover::get_circuit("runtime_spend").expect("runtime circuit");
+    assert_eq!(runtime.description, None);
+
+    catalog::clear();
+}
+
+#[test]
+fn embedded_entries_carry_version_and_acir_hash() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+
+    for embed in artifacts::embedded() {
+        let expected: [u8; 32] = Sha256::digest(embed.acir).into();
+        assert_eq!(
+            embed.acir_hash, expected,
+            "build.rs hash for {}",
+            embed.name
+        );
+        assert!(!embed.version.is_empty(), "{} has no version", embed.name);
+
+        let entry = prover::get_circuit(embed.name).expect("embedded circuit");
+        assert_eq!(entry.version, embed.version);
+        assert_eq!(entry.acir_hash, embed.acir_hash);
+    }
+
+    catalog::clear();
+}
+
+#[test]
+fn corrupted_acir_fails_checksum() {
+    let embed = artifacts::embedded()
+        .iter()
+        .find(|c| c.name == "utxo_spend")
+        .expect("find embedded spend circuit");
+    embed
+        .verify_checksum()
+        .expect("embedded ACIR matches its hash");
+
+    let mut corrupted = embed.acir.to_vec();
+    let last = corrupted.last_mut().expect("non-empty ACIR");
+    *last ^= 0x01;
+    let err = artifacts::verify_acir_checksum(embed.name, &corrupted, &embed.acir_hash)
+        .expect_err("corrupted ACIR must be rejected");
+    assert!(
+        err.to_string().contains("checksum mismatch"),
+        "unexpected error: {err}"
+    );
+}
+
 #[test]
 fn init_circuit_from_artifacts_populates_metadata() {
     let _lock = serial_guard();
@@ -42,6 +125,9 @@ fn init_circuit_from_artifacts_populates_metadata() {
         entry.key_id, [0u8; 32],
         "generated key id should be non-zero"
     );
+    let expected_hash: [u8; 32] = Sha256::digest(embed.acir).into();
+    assert_eq!(entry.acir_hash, expected_hash);
+    assert!(entry.version.is_empty(), "runtime circuits are unversioned");
 
     catalog::clear();
 }
@@ -64,6 +150,14 @@ fn helper_accessors_surface_cached_metadata() {
     let hash = prover::get_vk_hash_by_id(key_id).expect("vk hash");
     assert_ne!(hash, [0u8; 32], "vk hash should not be zero");
 
+    assert_eq!(prover::get_vk_bytes("utxo_spend").expect("vk by name"), vk);
+    assert_eq!(
+        prover::get_vk_hash("utxo_spend").expect("hash by name"),
+        hash
+    );
+    assert!(prover::get_vk_bytes("not_registered").is_err());
+    assert!(prover::get_vk_hash("not_registered").is_err());
+
     catalog::clear();
 }
 
@@ -86,11 +180,14 @@ fn get_vk_hash_backfills_missing_cache() {
     catalog::clear();
     catalog::insert(CircuitEntry {
         name: entry.name.clone(),
+        version: entry.version.clone(),
         acir: entry.acir.clone(),
+        acir_hash: entry.acir_hash,
         vk: entry.vk.clone(),
         abi: entry.abi.clone(),
         key_id: entry.key_id,
         vk_hash: None,
+        description: entry.description.clone(),
     });
 
     let recomputed = prover::get_vk_hash_by_id(entry.key_id).expect("recomputed hash");
@@ -120,11 +217,14 @@ fn get_vk_bytes_regenerates_missing_vk() {
     catalog::clear();
     catalog::insert(CircuitEntry {
         name: entry.name.clone(),
+        version: entry.version.clone(),
         acir: entry.acir.clone(),
+        acir_hash: entry.acir_hash,
         vk: Vec::new(),
         abi: entry.abi.clone(),
         key_id: entry.key_id,
         vk_hash: None,
+        description: entry.description.clone(),
     });
 
     let regenerated = prover::get_vk_bytes_by_id(entry.key_id).expect("vk bytes");
@@ -142,3 +242,219 @@ fn get_vk_bytes_regenerates_missing_vk() {
 
     catalog::clear();
 }
+
+#[test]
+fn circuit_stats_reports_non_zero_counts() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+
+    for name in ["utxo_spend", "utxo_merge"] {
+        let stats = prover::circuit_stats(name).expect("circuit stats");
+        assert!(stats.opcode_count > 0, "{name} has no opcodes");
+        assert!(stats.witness_count > 0, "{name} has no witnesses");
+        let entry = prover::get_circuit(name).expect("registered circuit");
+        assert_eq!(stats.acir_size_bytes, entry.acir.len());
+    }
+
+    catalog::clear();
+}
+
+#[test]
+fn init_with_precomputed_key_id_matches_compiled_id() {
+    let _lock = serial_guard();
+    catalog::clear();
+    let embed = artifacts::embedded()
+        .iter()
+        .find(|e| e.name == "utxo_merge")
+        .expect("embedded merge circuit");
+
+    prover::init_circuit_from_artifacts("merge_compiled", embed.acir, embed.vk, embed.abi_json)
+        .expect("compile and register");
+    let known_id = prover::get_key_id("merge_compiled").expect("compiled key id");
+
+    prover::init_circuit_from_artifacts_with_id(
+        "merge_reused",
+        embed.acir,
+        embed.vk,
+        embed.abi_json,
+        known_id,
+        false,
+    )
+    .expect("register with known id");
+    assert_eq!(
+        prover::get_key_id("merge_reused").expect("reused id"),
+        known_id
+    );
+
+    prover::init_circuit_from_artifacts_with_id(
+        "merge_validated",
+        embed.acir,
+        embed.vk,
+        embed.abi_json,
+        known_id,
+        true,
+    )
+    .expect("validated id matches");
+    assert_eq!(
+        prover::get_key_id("merge_validated").expect("validated id"),
+        known_id
+    );
+
+    let err = prover::init_circuit_from_artifacts_with_id(
+        "merge_bogus",
+        embed.acir,
+        embed.vk,
+        embed.abi_json,
+        [0xAB; 32],
+        true,
+    )
+    .expect_err("mismatched id rejected");
+    assert!(err.to_string().contains("does not match"), "{err}");
+    assert!(prover::get_circuit("merge_bogus").is_none());
+
+    catalog::clear();
+}
+
+#[test]
+fn regenerate_all_vks_keeps_catalog_consistent() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+    assert!(prover::vks_are_consistent().expect("check embedded vks"));
+
+    let regenerated = prover::regenerate_all_vks().expect("regenerate vks");
+    let mut names: Vec<&String> = regenerated.keys().collect();
+    names.sort();
+    assert_eq!(names, ["utxo_merge", "utxo_spend"]);
+    for (name, vk) in &regenerated {
+        assert!(!vk.is_empty(), "{name} vk empty");
+        assert_eq!(&prover::get_circuit(name).expect("entry").vk, vk);
+    }
+    assert!(prover::vks_are_consistent().expect("check regenerated vks"));
+
+    catalog::clear();
+}
+
+#[test]
+fn entries_report_abi_field_counts() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+
+    let spend = prover::get_circuit("utxo_spend").expect("embedded spend circuit");
+    // pk_x/pk_y (2) + sig64 (64) + msg32 (32) + three 10-field UTXOs + transfer (3).
+    assert_eq!(spend.private_input_field_count(), 131);
+    // Plus the single public leaf hash.
+    assert_eq!(spend.total_parameter_field_count(), 132);
+    assert!(spend.has_parameter("input.transfer.token"));
+    assert!(spend.has_parameter("public_inputs"));
+    assert!(!spend.has_parameter("input.transfer.missing"));
+
+    catalog::clear();
+}
+
+#[test]
+fn insert_many_registers_entries_from_concurrent_threads() {
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+    let template = prover::get_circuit("utxo_spend").expect("embedded spend circuit");
+
+    let handles: Vec<_> = ["left", "right"]
+        .into_iter()
+        .map(|prefix| {
+            let entries: Vec<CircuitEntry> = (0..4)
+                .map(|i| CircuitEntry {
+                    name: format!("{prefix}_{i}"),
+                    ..template.clone()
+                })
+                .collect();
+            std::thread::spawn(move || catalog::insert_many(entries))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().expect("insert thread");
+    }
+
+    let loaded = catalog::all_loaded();
+    for prefix in ["left", "right"] {
+        for i in 0..4 {
+            let name = format!("{prefix}_{i}");
+            assert!(loaded.contains(&name), "{name} missing from catalog");
+        }
+    }
+    catalog::clear();
+}
+
+#[test]
+fn readers_and_writers_share_the_catalog_concurrently() {
+    const READERS: usize = 8;
+    const WRITERS: usize = 2;
+    const ROUNDS: usize = 200;
+    const THREADS: usize = READERS + WRITERS;
+    const WRITTEN: usize = WRITERS * ROUNDS;
+
+    let _lock = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded");
+    let template = prover::get_circuit("utxo_spend").expect("embedded spend circuit");
+    let embedded = catalog::all_loaded().len();
+    let updated_vk = vec![0x5a; 8];
+    let barrier = std::sync::Arc::new(std::sync::Barrier::new(THREADS));
+
+    let readers: Vec<_> = (0..READERS)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let template = template.clone();
+            let updated_vk = updated_vk.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                for _ in 0..ROUNDS {
+                    let spend = catalog::get("utxo_spend").expect("spend stays registered");
+                    assert_eq!(spend.key_id, template.key_id);
+                    assert!(catalog::all_loaded().contains(&"utxo_spend".to_string()));
+                    for name in catalog::all_loaded() {
+                        // Entries vanish only on `clear`, which no thread calls here.
+                        let entry = catalog::peek(&name).expect("listed entry is readable");
+                        if name.starts_with("writer") {
+                            assert!(entry.vk == template.vk || entry.vk == updated_vk);
+                        }
+                    }
+                }
+            })
+        })
+        .collect();
+    let writers: Vec<_> = (0..WRITERS)
+        .map(|w| {
+            let barrier = barrier.clone();
+            let template = template.clone();
+            let updated_vk = updated_vk.clone();
+            std::thread::spawn(move || {
+                barrier.wait();
+                for i in 0..ROUNDS {
+                    let name = format!("writer{w}_{i}");
+                    // Distinct key ids keep the writers off the spend circuit's VK slot.
+                    let mut key_id = [0xee; 32];
+                    key_id[0] = w as u8;
+                    key_id[1] = i as u8;
+                    catalog::insert(CircuitEntry {
+                        name: name.clone(),
+                        key_id,
+                        ..template.clone()
+                    });
+                    catalog::update_vk(&name, &updated_vk, None, None);
+                }
+            })
+        })
+        .collect();
+    for handle in readers.into_iter().chain(writers) {
+        handle.join().expect("catalog thread");
+    }
+
+    let loaded = catalog::all_loaded();
+    assert_eq!(loaded.len(), embedded.saturating_add(WRITTEN));
+    let sample = catalog::peek("writer1_0").expect("writer entry");
+    assert_eq!(sample.vk, updated_vk);
+    catalog::clear();
+}
@@ -1,15 +1,21 @@
 #![allow(dead_code)]
 
+use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 
+use acir::AcirField;
+use usernode_circuits::CircuitFieldElement as FE;
+
 use aztec_barretenberg_rs::{
     grumpkin_derive_pubkey, mega_proof_fields_hash, mega_vk_hash, schnorr_blake2s_sign,
     schnorr_blake2s_verify_xy,
 };
 
 use usernode_circuits::bn254::Field;
-use usernode_circuits::poseidon2::{hash_fields, hash10};
-use usernode_circuits::prover::fetch_batch_public_inputs;
+use usernode_circuits::poseidon2::{hash_fields, hash_n};
+use usernode_circuits::prover::{
+    SchnorrEnc, SpendInputEnc, TransferEnc, UtxoEnc, fetch_batch_public_inputs,
+};
 
 fn guard() -> &'static Mutex<()> {
     static TEST_GUARD: OnceLock<Mutex<()>> = OnceLock::new();
@@ -34,7 +40,7 @@ pub struct Utxo {
 }
 
 pub fn utxo_commitment(utxo: &Utxo) -> Field {
-    hash10([
+    hash_n([
         utxo.recipient_pk_x,
         utxo.assets[0].token,
         utxo.assets[0].amount,
@@ -142,3 +148,126 @@ pub fn proof_hash(proof: &[u8], tag: u32) -> [u8; 32] {
 pub fn vk_hash(vk: &[u8]) -> [u8; 32] {
     mega_vk_hash(vk).expect("mega vk hash")
 }
+
+/// Build a valid, signed spend encoding: 40 of token 7 out of a 100-unit input
+/// with a fee of 2, mirroring `tests/utxo_spend.rs`.
+#[allow(dead_code)]
+pub fn sample_spend_enc() -> SpendInputEnc {
+    let sender = Keypair::from_seed([7u8; 32]);
+    let recipient = Keypair::from_seed([9u8; 32]);
+    let sender_pkx_field = Field::from_bytes(sender.pk_x_bytes());
+    let zero = Field::from(0u128);
+
+    let in_tokens = [Field::from(7u128), zero, zero, zero];
+    let in_amounts = [Field::from(100u128), zero, zero, zero];
+    let transfer_token = Field::from(7u128);
+    let transfer_amount = Field::from(40u128);
+    let fee_amount = Field::from(2u128);
+    let receiver_tokens = [transfer_token, zero, zero, zero];
+    let receiver_amounts = [transfer_amount, zero, zero, zero];
+    let remainder_amounts = [Field::from(58u128), zero, zero, zero];
+
+    let to_utxo = |tokens: [Field; 4], amounts: [Field; 4], pk_x: Field, salt: Field| Utxo {
+        assets: array_init::array_init(|idx| Asset {
+            token: *tokens.get(idx).expect("slot"),
+            amount: *amounts.get(idx).expect("slot"),
+        }),
+        recipient_pk_x: pk_x,
+        salt,
+    };
+    let receiver = to_utxo(
+        receiver_tokens,
+        receiver_amounts,
+        Field::from_bytes(recipient.pk_x_bytes()),
+        Field::from(1111u128),
+    );
+    let remainder = to_utxo(
+        in_tokens,
+        remainder_amounts,
+        sender_pkx_field,
+        Field::from(2222u128),
+    );
+
+    let msg32 = spend_digest(
+        sender_pkx_field,
+        transfer_token,
+        transfer_amount,
+        fee_amount,
+        utxo_commitment(&receiver),
+        utxo_commitment(&remainder),
+    );
+
+    SpendInputEnc {
+        schnorr: SchnorrEnc {
+            pk_x: sender.pk_x_bytes(),
+            pk_y: sender.pk_y_bytes(),
+            sig64: sender.sign(msg32),
+            msg32,
+        },
+        in0: UtxoEnc {
+            assets_tokens: in_tokens,
+            assets_amounts: in_amounts,
+            recipient_pk_x: sender.pk_x_bytes(),
+            salt: Field::from(3333u128),
+        },
+        transfer: TransferEnc {
+            token: transfer_token,
+            amount: transfer_amount,
+            fee: fee_amount,
+        },
+        receiver: UtxoEnc {
+            assets_tokens: receiver_tokens,
+            assets_amounts: receiver_amounts,
+            recipient_pk_x: recipient.pk_x_bytes(),
+            salt: receiver.salt,
+        },
+        remainder: UtxoEnc {
+            assets_tokens: in_tokens,
+            assets_amounts: remainder_amounts,
+            recipient_pk_x: sender.pk_x_bytes(),
+            salt: remainder.salt,
+        },
+    }
+}
+
+/// Key a spend encoding by Noir ABI path, as `prove_with_abi` expects.
+#[allow(dead_code)]
+pub fn spend_inputs_by_name(enc: &SpendInputEnc) -> HashMap<String, Vec<FE>> {
+    let bytes = |b: &[u8; 32]| vec![FE::from_be_bytes_reduce(b)];
+    let field = |f: &Field| vec![FE::from_be_bytes_reduce(f.as_ref())];
+    let fields = |fs: &[Field; 4]| {
+        fs.iter()
+            .map(|f| FE::from_be_bytes_reduce(f.as_ref()))
+            .collect::<Vec<_>>()
+    };
+    let byte_elems = |bs: &[u8]| bs.iter().map(|b| FE::from(*b as u128)).collect::<Vec<_>>();
+
+    let mut map = HashMap::new();
+    map.insert("input.schnorr.pk_x".into(), bytes(&enc.schnorr.pk_x));
+    map.insert("input.schnorr.pk_y".into(), bytes(&enc.schnorr.pk_y));
+    map.insert("input.schnorr.sig64".into(), byte_elems(&enc.schnorr.sig64));
+    map.insert("input.schnorr.msg32".into(), byte_elems(&enc.schnorr.msg32));
+    for (prefix, utxo) in [
+        ("input.in0", &enc.in0),
+        ("input.receiver", &enc.receiver),
+        ("input.remainder", &enc.remainder),
+    ] {
+        map.insert(
+            format!("{prefix}.assets_tokens"),
+            fields(&utxo.assets_tokens),
+        );
+        map.insert(
+            format!("{prefix}.assets_amounts"),
+            fields(&utxo.assets_amounts),
+        );
+        map.insert(
+            format!("{prefix}.recipient_pk_x"),
+            bytes(&utxo.recipient_pk_x),
+        );
+        map.insert(format!("{prefix}.salt"), field(&utxo.salt));
+    }
+    map.insert("input.transfer.token".into(), field(&enc.transfer.token));
+    map.insert("input.transfer.amount".into(), field(&enc.transfer.amount));
+    map.insert("input.transfer.fee".into(), field(&enc.transfer.fee));
+    map
+}
@@ -0,0 +1,18 @@
+//! Programmatic CRS directory configuration.
+
+#[test]
+fn set_crs_directory_before_init() {
+    let dir = tempfile::tempdir().expect("tempdir");
+    usernode_circuits::prover::set_crs_directory(dir.path()).expect("set crs directory");
+    assert_eq!(
+        usernode_circuits::prover::crs_directory().as_deref(),
+        Some(dir.path())
+    );
+
+    usernode_circuits::init_default_circuits().expect("init embedded circuits");
+    assert_eq!(
+        usernode_circuits::prover::crs_directory().as_deref(),
+        Some(dir.path()),
+        "initialisation must not override an explicit CRS directory"
+    );
+}
@@ -0,0 +1,68 @@
+//! JSON witness dumps for debugging failing proofs.
+
+mod common;
+
+use common::{sample_spend_enc, serial_guard, spend_inputs_by_name};
+use usernode_circuits::catalog;
+use usernode_circuits::field::from_acir_field;
+use usernode_circuits::prover::{
+    debug_witness_map, encode_spend_privates, get_circuit, init_default_circuits, public_outputs,
+    solve_witness,
+};
+
+#[test]
+fn debug_witness_map_emits_labelled_json() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let inputs = spend_inputs_by_name(&sample_spend_enc());
+    let dump = debug_witness_map("utxo_spend", &inputs).expect("solve witness");
+    let json: serde_json::Value = serde_json::from_str(&dump).expect("valid JSON");
+
+    assert_eq!(json.get("circuit"), Some(&"utxo_spend".into()));
+    let witnesses = json
+        .get("witnesses")
+        .and_then(|w| w.as_array())
+        .expect("witness array");
+    assert!(!witnesses.is_empty());
+    assert!(
+        witnesses.iter().any(|w| w["param"] == "input.schnorr.pk_x"),
+        "private witnesses should be labelled with their ABI path"
+    );
+    assert!(witnesses.iter().all(|w| {
+        w.get("value")
+            .and_then(|v| v.as_str())
+            .is_some_and(|v| v.starts_with("0x"))
+    }));
+
+    catalog::clear();
+}
+
+#[test]
+fn solve_witness_exposes_the_public_output_witnesses() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let privates = encode_spend_privates(&sample_spend_enc());
+    let map = solve_witness("utxo_spend", &privates, &[]).expect("solve witness");
+
+    let acir = get_circuit("utxo_spend").expect("spend circuit").acir;
+    let program: acir::circuit::Program<acir::FieldElement> =
+        acir::circuit::Program::deserialize_program(&acir).expect("embedded ACIR");
+    let main = program.functions.first().expect("main function");
+    let returned: Vec<_> = main
+        .return_values
+        .0
+        .iter()
+        .map(|w| from_acir_field(*map.get(w).expect("return witness is solved")))
+        .collect();
+    assert!(!returned.is_empty());
+    assert_eq!(
+        returned,
+        public_outputs("utxo_spend", &privates).expect("public outputs")
+    );
+
+    catalog::clear();
+}
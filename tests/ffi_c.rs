@@ -0,0 +1,58 @@
+//! Compile and run `tests/c/smoke.c` against the `cdylib` (requires `ffi`).
+//!
+//! The crate only builds an `rlib` by default, so build the shared library
+//! first with `cargo rustc --lib --crate-type cdylib --features ffi`.
+#![cfg(feature = "ffi")]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::poseidon2::hash_fields;
+
+/// Directory holding `libusernode_circuits.{so,dylib}` for this test build.
+fn target_profile_dir() -> PathBuf {
+    let exe = std::env::current_exe().expect("test executable path");
+    // target/<profile>/deps/<test-binary>
+    exe.parent()
+        .and_then(Path::parent)
+        .expect("target profile directory")
+        .to_path_buf()
+}
+
+#[test]
+fn c_program_links_and_runs() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let lib_dir = target_profile_dir();
+    let exe = Path::new(env!("CARGO_TARGET_TMPDIR")).join("usernode_ffi_smoke");
+
+    let status = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_owned()))
+        .arg(manifest_dir.join("tests/c/smoke.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg("-lusernode_circuits")
+        .arg("-o")
+        .arg(&exe)
+        .status()
+        .expect("spawn C compiler");
+    assert!(status.success(), "compiling smoke.c failed");
+
+    let output = Command::new(&exe)
+        .env("LD_LIBRARY_PATH", &lib_dir)
+        .env("DYLD_LIBRARY_PATH", &lib_dir)
+        .output()
+        .expect("run smoke binary");
+    assert!(
+        output.status.success(),
+        "smoke binary failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let expected = hash_fields(&[Field::from(1u128), Field::from(2u128)]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout).trim(),
+        hex::encode(expected.to_bytes())
+    );
+}
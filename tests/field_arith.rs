@@ -0,0 +1,82 @@
+//! Known-answer checks for `bn254::Field` arithmetic.
+//!
+//! CI runs this file with and without the `pure_rust` feature, so the
+//! Barretenberg and pure-Rust `Field` arithmetic must agree on every vector.
+
+use std::cmp::Ordering;
+
+use usernode_circuits::bn254::Field;
+
+/// BN254 scalar field modulus minus one, big-endian.
+const P_MINUS_ONE: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x00,
+];
+
+#[test]
+#[allow(clippy::arithmetic_side_effects)]
+fn addition_wraps_at_the_modulus() {
+    let max = Field::from_bytes(P_MINUS_ONE);
+    assert_eq!(Field::from(2u128) + Field::from(3u128), Field::from(5u128));
+    assert_eq!(max + Field::from(2u128), Field::one());
+
+    let mut acc = max;
+    acc += Field::one();
+    assert_eq!(acc, Field::zero());
+}
+
+#[test]
+#[allow(clippy::arithmetic_side_effects)]
+fn subtraction_wraps_below_zero() {
+    assert_eq!(Field::from(9u128) - Field::from(4u128), Field::from(5u128));
+    assert_eq!(Field::zero() - Field::one(), Field::from_bytes(P_MINUS_ONE));
+
+    let mut acc = Field::one();
+    acc -= Field::from(2u128);
+    assert_eq!(acc, Field::from_bytes(P_MINUS_ONE));
+}
+
+#[test]
+#[allow(clippy::arithmetic_side_effects)]
+fn multiplication_reduces_products() {
+    let max = Field::from_bytes(P_MINUS_ONE);
+    assert_eq!(Field::from(6u128) * Field::from(7u128), Field::from(42u128));
+    // (-1) * (-1) == 1 and (-1) * 2 == -2.
+    assert_eq!(max * max, Field::one());
+    assert_eq!(max * Field::from(2u128), max - Field::one());
+
+    let mut acc = Field::from(1u128 << 100);
+    acc *= Field::from(1u128 << 100);
+    assert_eq!(acc * Field::one(), acc);
+}
+
+#[test]
+fn comparison_orders_by_value() {
+    let max = Field::from_bytes(P_MINUS_ONE);
+    assert_eq!(
+        Field::from(1u128).partial_cmp(&Field::from(2u128)),
+        Some(Ordering::Less)
+    );
+    assert_eq!(max.partial_cmp(&Field::zero()), Some(Ordering::Greater));
+    assert_eq!(max.partial_cmp(&max), Some(Ordering::Equal));
+}
+
+#[test]
+#[allow(clippy::arithmetic_side_effects)]
+fn sum_and_product_match_manual_arithmetic() {
+    let max = Field::from_bytes(P_MINUS_ONE);
+    let values = vec![Field::from(3u128), max, Field::from(7u128)];
+
+    let manual_sum = Field::from(3u128) + max + Field::from(7u128);
+    assert_eq!(manual_sum, Field::from(9u128));
+    assert_eq!(values.iter().copied().sum::<Field>(), manual_sum);
+    assert_eq!(values.iter().sum::<Field>(), manual_sum);
+
+    let manual_product = Field::from(3u128) * max * Field::from(7u128);
+    assert_eq!(values.iter().copied().product::<Field>(), manual_product);
+    assert_eq!(manual_product, Field::zero() - Field::from(21u128));
+
+    let empty: [Field; 0] = [];
+    assert_eq!(empty.iter().sum::<Field>(), Field::zero());
+    assert_eq!(empty.into_iter().product::<Field>(), Field::one());
+}
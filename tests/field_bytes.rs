@@ -0,0 +1,96 @@
+//! Byte-order conversions for `bn254::Field`.
+
+use usernode_circuits::bn254::{Field, MODULUS_BE};
+
+#[test]
+fn little_endian_round_trip() {
+    let one = Field::from(1u128);
+    let le = one.to_bytes_le();
+    let expected: [u8; 32] = std::array::from_fn(|i| u8::from(i == 0));
+    assert_eq!(le, expected);
+    assert_eq!(Field::from_bytes_le(le), one);
+}
+
+#[test]
+fn little_endian_is_reversed_big_endian() {
+    let value = Field::from(0x0102_0304_0506_0708_090a_0b0c_0d0e_0f10u128);
+    let mut be = value.to_bytes();
+    be.reverse();
+    assert_eq!(value.to_bytes_le(), be);
+    assert_ne!(Field::from_bytes_le(value.to_bytes()), value);
+}
+
+#[test]
+fn acir_conversions_round_trip() {
+    use rand::RngCore;
+    use usernode_circuits::field::{
+        acir_fields_to_bn254, bn254_fields_to_acir, from_acir_field, to_acir_field,
+    };
+
+    let mut rng = rand::thread_rng();
+    let values: Vec<Field> = (0..100)
+        .map(|_| {
+            let mut bytes = [0u8; 32];
+            // Leave the top byte clear so every value is below the modulus.
+            if let Some((_, rest)) = bytes.split_first_mut() {
+                rng.fill_bytes(rest);
+            }
+            Field::from_bytes(bytes)
+        })
+        .collect();
+
+    let acir = bn254_fields_to_acir(&values);
+    assert_eq!(acir.len(), values.len());
+    assert_eq!(acir_fields_to_bn254(&acir), values);
+    for value in &values {
+        assert_eq!(from_acir_field(to_acir_field(*value)), *value);
+    }
+}
+
+#[test]
+fn checked_and_reduced_constructors_handle_the_modulus() {
+    let below = Field::from(12345u128).to_bytes();
+    assert_eq!(
+        Field::from_bytes_checked(below).expect("canonical"),
+        Field::from_bytes(below)
+    );
+    assert_eq!(
+        Field::from_be_bytes_reduced(below),
+        Field::from_bytes(below)
+    );
+
+    assert!(Field::from_bytes_checked(MODULUS_BE).is_err());
+    assert!(Field::from_bytes_checked([0xff; 32]).is_err());
+    assert_eq!(Field::from_be_bytes_reduced(MODULUS_BE), Field::zero());
+
+    // modulus + 1 aliases 1 once reduced.
+    let mut above = MODULUS_BE;
+    above[31] = 0x02;
+    assert_eq!(Field::from_be_bytes_reduced(above), Field::one());
+    assert_ne!(Field::from_bytes(above), Field::one());
+}
+
+#[test]
+fn display_is_zero_padded_lowercase_hex() {
+    let field = Field::from(255u128);
+    let shown = field.to_string();
+    assert_eq!(shown, format!("0x{}ff", "0".repeat(62)));
+    assert_eq!(shown, field.to_hex());
+    assert_eq!(shown.len(), 66);
+    assert_eq!(
+        Field::from_bytes([0xab; 32]).to_string(),
+        format!("0x{}", "ab".repeat(32))
+    );
+}
+
+#[test]
+fn binary_prints_all_256_bits() {
+    let bits = format!("{:b}", Field::from(5u128));
+    assert_eq!(bits.len(), 256);
+    assert!(bits.ends_with("00000101"));
+    assert!(bits.trim_end_matches("101").chars().all(|c| c == '0'));
+    assert_eq!(
+        format!("{:#b}", Field::zero()),
+        format!("0b{}", "0".repeat(256))
+    );
+}
@@ -0,0 +1,35 @@
+//! Field vector helpers against hand-computed values.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::field_vec::{field_dot, field_scale, field_sum, hash_vec};
+use usernode_circuits::poseidon2::hash_fields;
+
+fn fields(xs: &[u128]) -> Vec<Field> {
+    xs.iter().copied().map(Field::from).collect()
+}
+
+#[test]
+fn dot_product_matches_manual_value() {
+    // 1*4 + 2*5 + 3*6 = 32
+    let dot = field_dot(&fields(&[1, 2, 3]), &fields(&[4, 5, 6])).expect("equal lengths");
+    assert_eq!(dot, Field::from(32u128));
+    assert_eq!(field_dot(&[], &[]).expect("empty"), Field::zero());
+}
+
+#[test]
+fn dot_product_rejects_length_mismatch() {
+    assert!(field_dot(&fields(&[1, 2]), &fields(&[3])).is_err());
+}
+
+#[test]
+fn sum_scale_and_hash() {
+    assert_eq!(field_sum(&fields(&[10, 20, 12])), Field::from(42u128));
+    assert_eq!(field_sum(&[]), Field::zero());
+    assert_eq!(
+        field_scale(&fields(&[1, 2, 3]), Field::from(7u128)),
+        fields(&[7, 14, 21])
+    );
+
+    let v = fields(&[1, 2, 3]);
+    assert_eq!(hash_vec(&v), hash_fields(&v));
+}
@@ -0,0 +1,209 @@
+//! Key generation helpers on `Keypair`.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::keys::Keypair;
+use usernode_circuits::types::SchnorrPublicKey;
+
+#[test]
+fn from_entropy_generates_distinct_keys() {
+    let a = Keypair::from_entropy().expect("entropy keypair");
+    let b = Keypair::from_entropy().expect("entropy keypair");
+    assert_ne!(a.public_key_xonly(), b.public_key_xonly());
+}
+
+#[test]
+fn seed_bytes_restores_the_same_keypair() {
+    let original = Keypair::from_entropy().expect("entropy keypair");
+    let seed = original
+        .seed_bytes()
+        .expect("entropy keypairs keep their seed");
+    let restored = Keypair::from_seed(seed).expect("restore from seed");
+    assert_eq!(original.public_key_xy(), restored.public_key_xy());
+
+    let seeded = Keypair::from_seed([3u8; 32]).expect("seeded keypair");
+    assert_eq!(seeded.seed_bytes(), Some([3u8; 32]));
+}
+
+#[test]
+fn keypair_and_public_key_verify_agree() {
+    let signer = Keypair::test_pair(7);
+    let (pk_x, pk_y) = signer.public_key_xy();
+    let public_key = SchnorrPublicKey::new(pk_x, pk_y);
+
+    let msg32 = [42u8; 32];
+    let sig64 = signer.sign_prehash(msg32);
+    assert!(Keypair::verify_with_xy(pk_x, pk_y, msg32, sig64));
+    assert!(signer.verify(msg32, sig64));
+    assert!(public_key.verify(sig64, msg32));
+
+    let other = [43u8; 32];
+    assert!(!signer.verify(other, sig64));
+    assert!(!public_key.verify(sig64, other));
+}
+
+#[test]
+fn public_key_round_trips_through_fields_and_bytes() {
+    let signer = Keypair::test_pair(9);
+    let (pk_x, pk_y) = signer.public_key_xy();
+    let pk = SchnorrPublicKey::new(pk_x, pk_y);
+
+    assert_eq!(
+        SchnorrPublicKey::from_fields(pk.pk_x_field(), pk.pk_y_field()),
+        pk
+    );
+
+    let bytes = pk.to_bytes_64();
+    let (x, y) = bytes.split_at(32);
+    assert_eq!(x, pk_x);
+    assert_eq!(y, pk_y);
+    assert_eq!(SchnorrPublicKey::from_bytes_64(&bytes), pk);
+}
+
+#[test]
+fn formatting_does_not_leak_the_secret_key() {
+    let seed = [1u8; 32];
+    let keypair = Keypair::from_seed(seed).expect("derive keypair");
+    let seed_hex = hex::encode(seed);
+    let (pk_x, pk_y) = keypair.public_key_xy();
+
+    let debug = format!("{keypair:?}");
+    assert!(
+        !debug.contains(&seed_hex),
+        "debug output leaked sk: {debug}"
+    );
+    assert!(!debug.contains(&format!("{seed:?}")));
+    assert!(debug.contains(&format!("0x{}", hex::encode(pk_x))));
+    assert!(debug.contains(&format!("0x{}", hex::encode(pk_y))));
+
+    let display = keypair.to_string();
+    assert!(!display.contains(&seed_hex));
+    assert_eq!(display, format!("Keypair(pk_x=0x{})", hex::encode(pk_x)));
+
+    let pk_debug = format!("{:?}", SchnorrPublicKey::new(pk_x, pk_y));
+    assert!(pk_debug.contains(&format!("0x{}", hex::encode(pk_x))));
+    assert!(pk_debug.contains(&format!("0x{}", hex::encode(pk_y))));
+}
+
+#[test]
+fn ct_eq_matches_equality_on_both_coordinates() {
+    let (pk_x, pk_y) = Keypair::test_pair(3).public_key_xy();
+    let pk = SchnorrPublicKey::new(pk_x, pk_y);
+    assert_eq!(pk.ct_eq(&SchnorrPublicKey::new(pk_x, pk_y)).unwrap_u8(), 1);
+    assert_eq!(pk, SchnorrPublicKey::new(pk_x, pk_y));
+
+    let (other_x, other_y) = Keypair::test_pair(4).public_key_xy();
+    let other = SchnorrPublicKey::new(other_x, other_y);
+    assert_eq!(pk.ct_eq(&other).unwrap_u8(), 0);
+    assert_ne!(pk, other);
+
+    // A difference in only one coordinate is still a mismatch.
+    let mut last_byte = pk_y;
+    last_byte[31] ^= 1;
+    assert_eq!(
+        pk.ct_eq(&SchnorrPublicKey::new(pk_x, last_byte))
+            .unwrap_u8(),
+        0
+    );
+    assert_eq!(
+        pk.ct_eq(&SchnorrPublicKey::new(other_x, pk_y)).unwrap_u8(),
+        0
+    );
+}
+
+#[test]
+fn try_new_checks_the_point_is_on_grumpkin() {
+    let signer = Keypair::test_pair(5);
+    let (pk_x, pk_y) = signer.public_key_xy();
+    let pk = SchnorrPublicKey::try_new(pk_x, pk_y).expect("derived key is on the curve");
+    assert_eq!(pk, SchnorrPublicKey::new(pk_x, pk_y));
+
+    assert!(SchnorrPublicKey::try_new([0u8; 32], [0u8; 32]).is_err());
+
+    let (x, y) = (Field::from(1u128), Field::from(2u128));
+    assert!(SchnorrPublicKey::try_new(x.to_bytes(), y.to_bytes()).is_err());
+    // Flipping the y-coordinate's low byte takes a valid key off the curve.
+    let mut bad_y = pk_y;
+    bad_y[31] ^= 1;
+    assert!(SchnorrPublicKey::try_new(pk_x, bad_y).is_err());
+}
+
+#[test]
+fn viewing_key_is_deterministic_and_hides_the_scalar() {
+    let keypair = Keypair::test_pair(4);
+    let vk = keypair.viewing_key();
+    assert_eq!(vk, keypair.viewing_key());
+    assert_ne!(vk.ivk_x(), vk.ovk_x());
+
+    let other = Keypair::test_pair(6);
+    assert_ne!(vk, other.viewing_key());
+
+    let debug = format!("{vk:?}");
+    assert!(debug.contains(&format!("0x{}", hex::encode(vk.ivk_x()))));
+    assert!(debug.contains(&format!("0x{}", hex::encode(vk.ovk_x()))));
+    assert!(
+        !debug.contains("vk:"),
+        "debug output leaked the scalar: {debug}"
+    );
+}
+
+#[test]
+fn test_pairs_are_distinct_and_deterministic() {
+    let one = Keypair::test_pair(1);
+    let two = Keypair::test_pair(2);
+    assert_ne!(one.public_key_xy(), two.public_key_xy());
+    assert_eq!(one.public_key_xy(), Keypair::test_pair(1).public_key_xy());
+    assert_eq!(one.seed_bytes(), Some([1u8; 32]));
+}
+
+#[test]
+#[should_panic(expected = "all-zero seed")]
+fn test_pair_rejects_index_zero() {
+    let _ = Keypair::test_pair(0);
+}
+
+#[cfg(feature = "bip39")]
+#[test]
+fn generated_mnemonic_restores_the_keypair() {
+    let (phrase, keypair) = Keypair::generate_mnemonic().expect("generate mnemonic");
+    assert_eq!(phrase.split_whitespace().count(), 24);
+
+    let restored = Keypair::from_mnemonic(&phrase).expect("restore from mnemonic");
+    assert_eq!(restored.public_key_xy(), keypair.public_key_xy());
+}
+
+#[cfg(feature = "bip39")]
+#[test]
+fn mnemonic_seed_is_the_bip39_seed_prefix_for_every_length() {
+    // BIP-39 reference vectors (empty passphrase), first 32 bytes of the seed.
+    let cases = [
+        (
+            "abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon about",
+            "5eb00bbddcf069084889a8ab9155568165f5c453ccb85e70811aaed6f6da5fc1",
+        ),
+        (
+            "abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon abandon abandon art",
+            "408b285c123836004f4b8842c89324c1f01382450c0d439af345ba7fc49acf70",
+        ),
+    ];
+    for (phrase, seed_hex) in cases {
+        let mut seed = [0u8; 32];
+        hex::decode_to_slice(seed_hex, &mut seed).expect("seed hex");
+        let keypair = Keypair::from_mnemonic(phrase).expect("valid phrase");
+        assert_eq!(keypair.seed_bytes(), Some(seed));
+    }
+}
+
+#[cfg(feature = "bip39")]
+#[test]
+fn twelve_word_mnemonic_derives_a_stable_keypair() {
+    let phrase = "abandon abandon abandon abandon abandon abandon \
+                  abandon abandon abandon abandon abandon about";
+    let a = Keypair::from_mnemonic(phrase).expect("12-word phrase");
+    let b = Keypair::from_mnemonic(phrase).expect("12-word phrase");
+    assert_eq!(a.public_key_xy(), b.public_key_xy());
+
+    assert!(Keypair::from_mnemonic("abandon abandon about").is_err());
+}
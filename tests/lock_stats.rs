@@ -0,0 +1,73 @@
+//! Barretenberg lock contention counters (requires the `metrics` feature).
+#![cfg(feature = "metrics")]
+
+mod common;
+
+use common::serial_guard;
+use usernode_circuits::barretenberg::{lock_stats, reset_lock_stats};
+use usernode_circuits::catalog;
+use usernode_circuits::prover::{self, BatchProveRequest};
+
+#[test]
+fn parallel_proofs_record_acquisitions() {
+    let _guard = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded circuits");
+    let privates = prover::encode_spend_privates(&common::sample_spend_enc());
+
+    reset_lock_stats();
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let privates = privates.clone();
+            std::thread::spawn(move || prover::prove("utxo_spend", &privates))
+        })
+        .collect();
+    for handle in handles {
+        handle
+            .join()
+            .expect("thread join")
+            .expect("prove utxo_spend");
+    }
+
+    let stats = lock_stats();
+    assert!(
+        stats.acquisitions >= 2,
+        "expected at least two acquisitions, got {stats:?}"
+    );
+
+    reset_lock_stats();
+    assert_eq!(lock_stats().acquisitions, 0);
+    catalog::clear();
+}
+
+#[test]
+fn prove_batch_takes_the_lock_once() {
+    let _guard = serial_guard();
+    catalog::clear();
+    prover::init_default_circuits().expect("init embedded circuits");
+
+    let privates = prover::encode_spend_privates(&common::sample_spend_enc());
+    let request = BatchProveRequest {
+        circuit_name: "utxo_spend".to_owned(),
+        private_inputs: privates.clone(),
+    };
+
+    reset_lock_stats();
+    let proofs = prover::prove_batch(&[request.clone(), request]).expect("batch prove");
+    let batch_stats = lock_stats();
+
+    reset_lock_stats();
+    for _ in 0..2 {
+        prover::prove("utxo_spend", &privates).expect("prove");
+    }
+    let sequential_stats = lock_stats();
+
+    assert_eq!(proofs.len(), 2);
+    for proof in &proofs {
+        assert!(prover::verify("utxo_spend", proof).expect("verify"));
+    }
+    assert_eq!(batch_stats.acquisitions, 1);
+    assert_eq!(sequential_stats.acquisitions, 2);
+    assert!(batch_stats.acquisitions < sequential_stats.acquisitions);
+    catalog::clear();
+}
@@ -0,0 +1,69 @@
+//! Incremental Merkle trees of configurable depth.
+
+use usernode_circuits::batch::{BindingLeaf, compute_new_root, plan_block_with_depth};
+use usernode_circuits::bn254::Field;
+use usernode_circuits::catalog;
+use usernode_circuits::merkle::IncrementalMerkleTree;
+use usernode_circuits::poseidon2::h2;
+
+fn leaves(count: u128) -> Vec<Field> {
+    (1..=count).map(Field::from).collect()
+}
+
+fn check_depth<const D: usize>(count: u128) {
+    let mut tree = IncrementalMerkleTree::<D>::new();
+    assert!(tree.is_empty());
+    assert_eq!(
+        compute_new_root(&tree, &[]).expect("no leaves"),
+        tree.root()
+    );
+
+    // Preview the root over two appends, then check the tree agrees.
+    let values = leaves(count);
+    let (first, rest) = values.split_at(values.len().saturating_div(2));
+    let preview = compute_new_root(&tree, first).expect("leaves fit");
+    tree.extend(first).expect("leaves fit");
+    assert_eq!(tree.root(), preview);
+    let preview = compute_new_root(&tree, rest).expect("leaves fit");
+    tree.extend(rest).expect("leaves fit");
+    assert_eq!(tree.len(), values.len() as u64);
+    assert_eq!(tree.root(), preview);
+}
+
+#[test]
+fn roots_match_compute_new_root_at_several_depths() {
+    check_depth::<4>(16);
+    check_depth::<4>(5);
+    check_depth::<8>(37);
+    check_depth::<16>(9);
+}
+
+#[test]
+fn depth_one_root_is_the_parent_hash() {
+    let mut tree = IncrementalMerkleTree::<1>::default();
+    assert_eq!(tree.append(Field::from(3u128)).expect("slot 0"), 0);
+    assert_eq!(tree.append(Field::from(4u128)).expect("slot 1"), 1);
+    assert_eq!(tree.root(), h2(Field::from(3u128), Field::from(4u128)));
+    assert!(tree.append(Field::from(5u128)).is_err(), "tree is full");
+    assert_eq!(tree.len(), tree.capacity());
+}
+
+#[test]
+fn block_planning_respects_tree_depth() {
+    let block_leaves: Vec<BindingLeaf> = leaves(6)
+        .into_iter()
+        .map(|leaf_hash| BindingLeaf {
+            leaf_id: leaf_hash.to_bytes().to_vec(),
+            leaf_hash,
+        })
+        .collect();
+
+    let block = plan_block_with_depth(1, Field::zero(), block_leaves.clone(), 3)
+        .expect("six leaves fit in depth 3");
+    assert_eq!(block.leaves.len(), 6);
+    assert!(plan_block_with_depth(1, Field::zero(), block_leaves.clone(), 2).is_err());
+    assert!(plan_block_with_depth(1, Field::zero(), block_leaves, 33).is_err());
+
+    let depth = catalog::utxo_tree_depth();
+    assert!((1..=32).contains(&depth));
+}
@@ -0,0 +1,82 @@
+//! Encrypting notes to a viewing key and reading them back.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::keys::{IncomingViewingKey, Keypair};
+use usernode_circuits::note_enc::encrypt;
+use usernode_circuits::types::{Asset, Utxo};
+
+fn sample_utxo(recipient: &Keypair) -> Utxo {
+    Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(40u128),
+            },
+            Asset {
+                token: Field::from(9u128),
+                amount: Field::from(3u128),
+            },
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from_bytes(recipient.public_key_xonly()),
+        salt: Field::from(1111u128),
+    }
+}
+
+#[test]
+fn encrypted_note_round_trips_for_the_recipient() {
+    let recipient = Keypair::test_pair(9);
+    let viewing_key = recipient.viewing_key();
+    let utxo = sample_utxo(&recipient);
+
+    let note = encrypt(&utxo, &viewing_key.incoming()).expect("encrypt note");
+    assert_eq!(viewing_key.try_decrypt(&note), Some(utxo.clone()));
+
+    // Fresh ephemeral keys make every encryption distinct.
+    let again = encrypt(&utxo, &viewing_key.incoming()).expect("encrypt note");
+    assert_ne!(note.ephemeral_pk_x, again.ephemeral_pk_x);
+    assert_ne!(note.ciphertext, again.ciphertext);
+}
+
+#[test]
+fn other_keys_and_tampering_are_rejected() {
+    let recipient = Keypair::test_pair(9);
+    let outsider = Keypair::test_pair(10);
+    let viewing_key = recipient.viewing_key();
+    let note = encrypt(&sample_utxo(&recipient), &viewing_key.incoming()).expect("encrypt note");
+
+    assert_eq!(outsider.viewing_key().try_decrypt(&note), None);
+
+    // Moving the ephemeral key off the curve is rejected before key agreement.
+    let mut off_curve = note.clone();
+    off_curve.ephemeral_pk_y[31] ^= 1;
+    assert_eq!(viewing_key.try_decrypt(&off_curve), None);
+
+    let mut tampered = note.clone();
+    if let Some(byte) = tampered.ciphertext.first_mut() {
+        *byte ^= 1;
+    }
+    assert_eq!(viewing_key.try_decrypt(&tampered), None);
+
+    let mut truncated = note;
+    truncated.ciphertext.pop();
+    assert_eq!(viewing_key.try_decrypt(&truncated), None);
+}
+
+#[test]
+fn incoming_key_is_a_curve_point() {
+    let incoming = Keypair::test_pair(9).viewing_key().incoming();
+    let rebuilt = IncomingViewingKey::try_new(incoming.x(), incoming.y()).expect("on curve");
+    assert_eq!(rebuilt, incoming);
+
+    let mut bad_y = incoming.y();
+    bad_y[31] ^= 1;
+    assert!(IncomingViewingKey::try_new(incoming.x(), bad_y).is_err());
+
+    // Encrypting to a forged off-curve key fails instead of leaking the note.
+    let forged: IncomingViewingKey =
+        serde_json::from_value(serde_json::json!({ "x": [1u8; 32], "y": [2u8; 32] }))
+            .expect("deserialize key");
+    assert!(encrypt(&sample_utxo(&Keypair::test_pair(9)), &forged).is_err());
+}
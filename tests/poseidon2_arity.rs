@@ -0,0 +1,58 @@
+//! The const-generic and domain-tagged helpers must agree with `hash_fields`.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::poseidon2::{h2, hash_fields, hash_n, hash2};
+
+fn fields<const N: usize>() -> [Field; N] {
+    std::array::from_fn(|i| Field::from((i as u128).saturating_add(1)))
+}
+
+#[test]
+#[allow(deprecated)]
+fn hash_n_matches_hash6_and_hash10() {
+    use usernode_circuits::poseidon2::{hash6, hash10};
+
+    let six = fields::<6>();
+    assert_eq!(hash_n(six), hash6(six));
+    let ten = fields::<10>();
+    assert_eq!(hash_n(ten), hash10(ten));
+}
+
+#[test]
+fn hash2_is_untagged() {
+    let [a, b] = fields::<2>();
+    assert_eq!(hash2(a, b), hash_fields(&[a, b]));
+    assert_ne!(hash2(a, b), h2(a, b));
+}
+
+#[test]
+fn hash_with_domain_prepends_the_tag() {
+    use usernode_circuits::poseidon2::{hash_with_domain, hash_with_domain_const};
+
+    let inputs = fields::<3>();
+    let [a, b, c] = inputs;
+    let manual = hash_fields(&[Field::from(77u128), a, b, c]);
+    assert_eq!(hash_with_domain(77, &inputs), manual);
+    assert_eq!(hash_with_domain_const::<77>(&inputs), manual);
+    assert_ne!(hash_with_domain(78, &inputs), manual);
+    assert_eq!(
+        hash_with_domain(77, &[]),
+        hash_fields(&[Field::from(77u128)])
+    );
+}
+
+#[test]
+fn hash_merge_leaf_n_generalises_the_two_input_leaf() {
+    use usernode_circuits::poseidon2::{hash_merge_leaf, hash_merge_leaf_n};
+
+    let [a, b, c, out] = fields::<4>();
+    assert_eq!(hash_merge_leaf_n(&[a, b], out), hash_merge_leaf(a, b, out));
+    assert_ne!(
+        hash_merge_leaf_n(&[a, b, c], out),
+        hash_merge_leaf(a, b, out)
+    );
+    assert_eq!(
+        hash_merge_leaf_n(&[a, b, c], out),
+        hash_fields(&[Field::from(12u128), a, b, c, out])
+    );
+}
@@ -0,0 +1,32 @@
+//! `h2_batch` must agree with element-wise `h2`.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::poseidon2::{h2, h2_batch, h2_ref};
+
+#[test]
+fn h2_batch_matches_elementwise_h2() {
+    let pairs: Vec<(Field, Field)> = (0..8u128)
+        .map(|i| {
+            (
+                Field::from(i),
+                Field::from(i.saturating_mul(31).saturating_add(5)),
+            )
+        })
+        .collect();
+
+    let expected: Vec<Field> = pairs.iter().map(|&(l, r)| h2(l, r)).collect();
+    assert_eq!(h2_batch(&pairs), expected);
+    assert!(h2_batch(&[]).is_empty());
+}
+
+#[test]
+fn h2_ref_matches_h2_over_a_vec() {
+    let leaves: Vec<Field> = (0..16u128).map(Field::from).collect();
+    let pairs = leaves.chunks_exact(2).filter_map(|pair| match pair {
+        [l, r] => Some((l, r)),
+        _ => None,
+    });
+    for (l, r) in pairs {
+        assert_eq!(h2(*l, *r), h2_ref(l, r));
+    }
+}
@@ -0,0 +1,39 @@
+//! Keyed PRF and MAC helpers.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::poseidon2::{MAC_TAG, PRF_TAG, hash_fields, mac, prf, prf_many};
+
+#[test]
+fn prf_depends_on_key_and_input() {
+    let input = Field::from(1234u128);
+    let a = prf(Field::from(1u128), input);
+    let b = prf(Field::from(2u128), input);
+    assert_ne!(a, b, "different keys must give different outputs");
+    assert_ne!(a, input);
+    assert_ne!(a, prf(Field::from(1u128), Field::from(1235u128)));
+    assert_eq!(
+        a,
+        hash_fields(&[Field::from(PRF_TAG), Field::from(1u128), input])
+    );
+}
+
+#[test]
+fn prf_many_matches_prf() {
+    let key = Field::from(99u128);
+    let inputs: Vec<Field> = (0..4u128).map(Field::from).collect();
+    let expected: Vec<Field> = inputs.iter().map(|&x| prf(key, x)).collect();
+    assert_eq!(prf_many(key, &inputs), expected);
+}
+
+#[test]
+fn mac_binds_key_and_message() {
+    let key = Field::from(5u128);
+    let message = [Field::from(10u128), Field::from(20u128)];
+    let tag = mac(key, &message);
+    assert_eq!(
+        tag,
+        hash_fields(&[Field::from(MAC_TAG), key, hash_fields(&message)])
+    );
+    assert_ne!(tag, mac(Field::from(6u128), &message));
+    assert_ne!(tag, mac(key, &[Field::from(10u128)]));
+}
@@ -0,0 +1,22 @@
+//! `proof_fields_hash` matches the raw Barretenberg helper.
+
+mod common;
+
+use common::{proof_hash, sample_spend_enc, serial_guard};
+use usernode_circuits::catalog;
+use usernode_circuits::prover::{encode_spend_privates, init_default_circuits, prove};
+
+#[test]
+fn proof_fields_hash_matches_direct_call() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let privates = encode_spend_privates(&sample_spend_enc());
+    let proof = prove("utxo_spend", &privates).expect("prove utxo_spend");
+
+    let hash = usernode_circuits::proof_fields_hash(&proof, 60).expect("proof fields hash");
+    assert_eq!(hash, proof_hash(&proof, 60));
+    assert_ne!(hash, [0u8; 32]);
+    catalog::clear();
+}
@@ -0,0 +1,38 @@
+//! Versioned proof envelopes produced by the transaction API.
+
+use usernode_circuits::prover::{
+    PROOF_FORMAT_VERSION, unwrap_proof, unwrap_proof_version, verify_tx, wrap_proof,
+};
+
+#[test]
+fn wrapped_proofs_round_trip_their_version() {
+    let proof = [0xab_u8; 40];
+    let wrapped = wrap_proof(&proof, 7);
+    assert_eq!(wrapped.len(), proof.len().saturating_add(4));
+    assert_eq!(wrapped.get(..4), Some(&[0u8, 0, 0, 7][..]));
+
+    let (version, bare) = unwrap_proof(&wrapped).expect("unwrap");
+    assert_eq!(version, 7);
+    assert_eq!(bare, proof);
+    assert_eq!(
+        unwrap_proof_version(&wrapped, 7).expect("matching version"),
+        proof
+    );
+
+    assert!(unwrap_proof(&[0u8; 3]).is_err());
+    assert_eq!(
+        unwrap_proof(&[0u8; 4]).expect("empty proof").1,
+        &[] as &[u8]
+    );
+}
+
+#[test]
+fn mismatched_versions_are_rejected() {
+    let wrapped = wrap_proof(&[1u8; 16], 2);
+    let err = unwrap_proof_version(&wrapped, 3).expect_err("version 2 is not 3");
+    assert!(err.to_string().contains("version 2"), "{err}");
+
+    // `verify_tx` refuses stale envelopes before looking up the circuit.
+    let stale = wrap_proof(&[1u8; 16], PROOF_FORMAT_VERSION.wrapping_add(1));
+    assert!(verify_tx("utxo_spend", &stale).is_err());
+}
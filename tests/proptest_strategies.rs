@@ -0,0 +1,22 @@
+//! Property tests driven by the `proptest_support` strategies.
+
+#![cfg(feature = "proptest")]
+
+use proptest::prelude::*;
+use usernode_circuits::bn254::{Field, MODULUS_BE};
+use usernode_circuits::types::Utxo;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn arbitrary_fields_are_canonical(field in any::<Field>()) {
+        prop_assert!(field.to_bytes() < MODULUS_BE);
+        prop_assert_eq!(Field::from_bytes_checked(field.to_bytes()).ok(), Some(field));
+    }
+
+    #[test]
+    fn arbitrary_utxo_commitments_are_nonzero(utxo in any::<Utxo>()) {
+        prop_assert_ne!(utxo.commitment(), Field::zero());
+    }
+}
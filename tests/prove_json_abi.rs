@@ -0,0 +1,81 @@
+//! Proving from ABI inputs supplied as a JSON object.
+
+mod common;
+
+use common::{sample_spend_enc, serial_guard, spend_inputs_by_name};
+use usernode_circuits::bn254::MODULUS_BE;
+use usernode_circuits::catalog;
+use usernode_circuits::field::{self, CircuitFieldElement};
+use usernode_circuits::prover::{init_default_circuits, prove_with_json_abi, verify};
+
+/// Decimal rendering of a big-endian integer, by repeated division by ten.
+#[allow(clippy::arithmetic_side_effects)]
+fn decimal(mut be: [u8; 32]) -> String {
+    let mut digits = Vec::new();
+    while be.iter().any(|&b| b != 0) {
+        let mut rem = 0u16;
+        for byte in &mut be {
+            let cur = (rem << 8) | u16::from(*byte);
+            *byte = u8::try_from(cur / 10).expect("quotient fits a byte");
+            rem = cur % 10;
+        }
+        digits.push(char::from(b'0' + u8::try_from(rem).expect("digit")));
+    }
+    if digits.is_empty() {
+        digits.push('0');
+    }
+    digits.iter().rev().collect()
+}
+
+fn inputs_json(render: impl Fn(&CircuitFieldElement) -> String) -> String {
+    let object: serde_json::Map<String, serde_json::Value> =
+        spend_inputs_by_name(&sample_spend_enc())
+            .into_iter()
+            .map(|(key, elems)| {
+                // Single-element inputs exercise the scalar form.
+                let value = match elems.as_slice() {
+                    [one] => render(one).into(),
+                    many => many.iter().map(|fe| render(fe).into()).collect(),
+                };
+                (key, value)
+            })
+            .collect();
+    serde_json::Value::Object(object).to_string()
+}
+
+#[test]
+fn spend_proves_from_json_inputs() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let hex = inputs_json(|fe| format!("0x{}", hex::encode(field::to_be_bytes(*fe))));
+    let proof = prove_with_json_abi("utxo_spend", &hex).expect("prove from hex JSON");
+    assert!(verify("utxo_spend", &proof).expect("verify"));
+
+    // Full-width values in decimal, as Prover.toml writes them.
+    let dec = inputs_json(|fe| decimal(field::to_be_bytes(*fe)));
+    let proof = prove_with_json_abi("utxo_spend", &dec).expect("prove from decimal JSON");
+    assert!(verify("utxo_spend", &proof).expect("verify"));
+
+    assert!(prove_with_json_abi("utxo_spend", "{\"input.schnorr.pk_x\": null}").is_err());
+    catalog::clear();
+}
+
+#[test]
+fn json_values_at_or_above_the_modulus_are_rejected() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    for value in [
+        format!("0x{}", hex::encode(MODULUS_BE)),
+        decimal(MODULUS_BE),
+        "1".repeat(78),
+    ] {
+        let json = format!("{{\"input.schnorr.pk_x\": \"{value}\"}}");
+        let err = prove_with_json_abi("utxo_spend", &json).expect_err("out of range");
+        assert!(format!("{err:#}").contains(&value), "{err:#}");
+    }
+    catalog::clear();
+}
@@ -0,0 +1,42 @@
+//! Hex round-trips for proof and verifying key bytes.
+
+mod common;
+
+use common::{sample_spend_enc, serial_guard};
+use usernode_circuits::catalog;
+use usernode_circuits::prover::{
+    encode_spend_privates, get_circuit, init_default_circuits, proof_from_hex, proof_to_hex, prove,
+    verify, verify_with_vk_bytes, vk_from_hex, vk_to_hex,
+};
+
+#[test]
+fn spend_proof_round_trips_through_hex() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let privates = encode_spend_privates(&sample_spend_enc());
+    let proof = prove("utxo_spend", &privates).expect("prove utxo_spend");
+
+    let encoded = proof_to_hex(&proof);
+    assert!(encoded.starts_with("0x"));
+    let decoded = proof_from_hex(&encoded).expect("decode proof hex");
+    assert_eq!(decoded, proof);
+    assert!(verify("utxo_spend", &decoded).expect("verify decoded proof"));
+
+    let unprefixed = encoded.trim_start_matches("0x");
+    assert_eq!(proof_from_hex(unprefixed).expect("decode bare hex"), proof);
+
+    let vk = get_circuit("utxo_spend").expect("spend circuit").vk;
+    let vk_decoded = vk_from_hex(&vk_to_hex(&vk)).expect("decode vk hex");
+    assert_eq!(vk_decoded, vk);
+    assert!(verify_with_vk_bytes(&decoded, &vk_decoded).expect("verify with decoded vk"));
+
+    catalog::clear();
+}
+
+#[test]
+fn invalid_hex_is_rejected() {
+    assert!(proof_from_hex("0xzz").is_err());
+    assert!(vk_from_hex("abc").is_err(), "odd-length hex must fail");
+}
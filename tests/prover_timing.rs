@@ -0,0 +1,26 @@
+//! Timed proving wrappers report a measurable duration.
+
+mod common;
+
+use common::{sample_spend_enc, serial_guard};
+use usernode_circuits::catalog;
+use usernode_circuits::prover::{
+    encode_spend_privates, init_default_circuits, prove_timed, verify,
+};
+
+#[test]
+fn prove_timed_reports_duration() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let privates = encode_spend_privates(&sample_spend_enc());
+    let (proof, duration) = prove_timed("utxo_spend", &privates).expect("prove utxo_spend");
+    assert!(
+        duration.as_millis() > 0,
+        "proving should take measurable time"
+    );
+    assert!(verify("utxo_spend", &proof).expect("verify utxo_spend"));
+
+    catalog::clear();
+}
@@ -0,0 +1,260 @@
+//! Convenience accessors on the shared domain types.
+
+use usernode_circuits::bn254::Field;
+use usernode_circuits::keys::Keypair;
+use usernode_circuits::types::{
+    Asset, MergeInput, MergeOutputCommit, MergeTx, SchnorrPublicKey, SpendInput,
+    SpendOutputCommits, SpendTx, TransactionOutput, Utxo, compute_merge_output,
+};
+
+fn utxo(amount: u128, salt: u128) -> Utxo {
+    Utxo {
+        assets: [
+            Asset {
+                token: Field::from(7u128),
+                amount: Field::from(amount),
+            },
+            Asset::empty(),
+            Asset::empty(),
+            Asset::empty(),
+        ],
+        recipient_pk_x: Field::from(42u128),
+        salt: Field::from(salt),
+    }
+}
+
+#[test]
+fn transaction_output_predicates_and_accessors() {
+    let spend = TransactionOutput::Spend {
+        receiver: utxo(40, 1),
+        remainder: utxo(58, 2),
+    };
+    assert!(spend.is_spend());
+    assert!(!spend.is_merge());
+    assert_eq!(spend.as_spend(), Some((&utxo(40, 1), &utxo(58, 2))));
+    assert_eq!(spend.as_merge(), None);
+
+    let merge = TransactionOutput::Merge { utxo: utxo(100, 3) };
+    assert!(merge.is_merge());
+    assert!(!merge.is_spend());
+    assert_eq!(merge.as_merge(), Some(&utxo(100, 3)));
+    assert_eq!(merge.as_spend(), None);
+}
+
+#[test]
+fn single_asset_constructor_zeroes_trailing_slots() {
+    let manual = utxo(100, 9);
+    let built = Utxo::with_single_asset(
+        Field::from(42u128),
+        Field::from(7u128),
+        Field::from(100u128),
+        Field::from(9u128),
+    );
+    assert_eq!(built, manual);
+    assert_eq!(built.commitment(), manual.commitment());
+}
+
+#[test]
+fn asset_slice_constructor_pads_and_rejects_overflow() {
+    let token = Asset {
+        token: Field::from(7u128),
+        amount: Field::from(100u128),
+    };
+    let padded = Utxo::with_assets_and_salt(&[token], Field::from(42u128), Field::from(9u128))
+        .expect("one asset fits");
+    assert_eq!(padded.commitment(), utxo(100, 9).commitment());
+
+    let empty = Utxo::with_assets_and_salt(&[], Field::from(42u128), Field::from(9u128))
+        .expect("no assets is allowed");
+    assert_eq!(empty.assets, [Asset::empty(); 4]);
+
+    assert!(Utxo::with_assets_and_salt(&[token; 5], Field::from(42u128), Field::zero()).is_err());
+}
+
+#[test]
+fn display_summarises_without_proof_bytes() {
+    let signer = SchnorrPublicKey::new([0xab; 32], [2u8; 32]);
+    let receiver = utxo(40, 1);
+    let remainder = utxo(58, 2);
+    let spend = SpendTx {
+        input: SpendInput::new(utxo(100, 3), signer),
+        expected_out_commits: SpendOutputCommits {
+            receiver: receiver.commitment(),
+            remainder: remainder.commitment(),
+        },
+        outputs: TransactionOutput::Spend {
+            receiver,
+            remainder,
+        },
+        proof: vec![0xcd; 96],
+        transfer_token: Field::from(7u128),
+        transfer_amount: Field::from(40u128),
+        fee_amount: Field::from(2u128),
+        signature: [0u8; 64],
+        msg32: [0u8; 32],
+        digest: Field::zero(),
+        receiver_note: None,
+        remainder_note: None,
+        salt_attempts: 1,
+    };
+    let line = spend.to_string();
+    assert_eq!(
+        line,
+        "SpendTx { from: 0xabababab…, transfer: 40 of token 7, fee: 2, proof: 96B }"
+    );
+    assert!(!line.contains("cdcd"));
+
+    let out = utxo(100, 6);
+    let merge = MergeTx {
+        inputs: [
+            MergeInput::new(utxo(60, 4), signer),
+            MergeInput::new(utxo(40, 5), signer),
+        ],
+        expected_out_commit: MergeOutputCommit(out.commitment()),
+        outputs: TransactionOutput::Merge { utxo: out },
+        proof: vec![0xcd; 96],
+        signature: [0u8; 64],
+        msg32: [0u8; 32],
+        digest: Field::zero(),
+    };
+    let line = merge.to_string();
+    assert!(line.starts_with("MergeTx { from: 0xabababab…, inputs: 2, out: 0x"));
+    assert!(line.ends_with("proof: 96B }"));
+    assert!(!line.contains("cdcd"));
+}
+
+#[test]
+fn defaults_are_zeroed_placeholders() {
+    assert_eq!(Asset::default(), Asset::empty());
+
+    let utxo = Utxo::default();
+    assert_eq!(utxo.assets, [Asset::empty(); 4]);
+    assert_eq!(utxo.recipient_pk_x, Field::zero());
+    assert_eq!(utxo.salt, Field::zero());
+    assert_eq!(utxo.commitment(), Utxo::default().commitment());
+    assert_eq!(
+        utxo.commitment(),
+        Utxo::with_single_asset(Field::zero(), Field::zero(), Field::zero(), Field::zero())
+            .commitment()
+    );
+
+    let signer = SchnorrPublicKey::default();
+    assert_eq!(signer.to_bytes_64(), [0u8; 64]);
+    assert!(SchnorrPublicKey::try_new(signer.pk_x_bytes(), signer.pk_y_bytes()).is_err());
+
+    assert_eq!(
+        SpendInput::default(),
+        SpendInput::new(Utxo::default(), signer)
+    );
+    assert_eq!(
+        MergeInput::default(),
+        MergeInput::new(Utxo::default(), signer)
+    );
+}
+
+#[test]
+fn default_inputs_do_not_round_trip() {
+    use binprot::{BinProtRead, BinProtWrite};
+
+    let mut bytes = Vec::new();
+    SpendInput::default()
+        .binprot_write(&mut bytes)
+        .expect("encode default spend input");
+    assert!(SpendInput::binprot_read(&mut bytes.as_slice()).is_err());
+    let json = serde_json::to_string(&SpendInput::default()).expect("serialize spend input");
+    assert!(serde_json::from_str::<SpendInput>(&json).is_err());
+
+    let mut bytes = Vec::new();
+    MergeInput::default()
+        .binprot_write(&mut bytes)
+        .expect("encode default merge input");
+    assert!(MergeInput::binprot_read(&mut bytes.as_slice()).is_err());
+    let json = serde_json::to_string(&MergeInput::default()).expect("serialize merge input");
+    assert!(serde_json::from_str::<MergeInput>(&json).is_err());
+}
+
+#[test]
+fn json_rejects_off_curve_public_keys() {
+    let (pk_x, pk_y) = Keypair::test_pair(3).public_key_xy();
+    let key = SchnorrPublicKey::new(pk_x, pk_y);
+    let json = serde_json::to_string(&key).expect("serialize key");
+    let decoded: SchnorrPublicKey = serde_json::from_str(&json).expect("on-curve key");
+    assert_eq!(decoded, key);
+
+    let off_curve = serde_json::to_string(&SchnorrPublicKey::new([0xab; 32], [2u8; 32]))
+        .expect("serialize off-curve key");
+    assert!(serde_json::from_str::<SchnorrPublicKey>(&off_curve).is_err());
+}
+
+#[test]
+fn output_commit_wrappers_keep_their_order() {
+    let commits = SpendOutputCommits {
+        receiver: utxo(40, 1).commitment(),
+        remainder: utxo(58, 2).commitment(),
+    };
+    assert_eq!(commits.to_array(), [commits.receiver, commits.remainder]);
+    assert_eq!(<[Field; 2]>::from(commits), commits.to_array());
+
+    let out = utxo(100, 6).commitment();
+    let wrapped = MergeOutputCommit::from(out);
+    assert_eq!(wrapped, MergeOutputCommit(out));
+    assert_eq!(wrapped.field(), out);
+    assert_eq!(Field::from(wrapped), out);
+}
+
+#[test]
+fn empty_slots_do_not_count_as_duplicate_tokens() {
+    assert!(!utxo(100, 1).has_duplicate_tokens());
+    assert!(!Utxo::default().has_duplicate_tokens());
+
+    let mut doubled = utxo(100, 1);
+    doubled.assets[3] = doubled.assets[0];
+    assert!(doubled.has_duplicate_tokens());
+}
+
+#[test]
+fn compute_merge_output_sums_aligned_slots() {
+    let two_tokens = |a: u128, b: u128| {
+        Utxo::with_assets_and_salt(
+            &[
+                Asset {
+                    token: Field::from(7u128),
+                    amount: Field::from(a),
+                },
+                Asset {
+                    token: Field::from(9u128),
+                    amount: Field::from(b),
+                },
+            ],
+            Field::from(42u128),
+            Field::from(a),
+        )
+        .expect("two assets fit")
+    };
+
+    let (tokens, amounts) =
+        compute_merge_output(&two_tokens(60, 5), &two_tokens(40, 6)).expect("aligned tokens");
+    assert_eq!(
+        tokens,
+        [
+            Field::from(7u128),
+            Field::from(9u128),
+            Field::zero(),
+            Field::zero()
+        ]
+    );
+    assert_eq!(
+        amounts,
+        [
+            Field::from(100u128),
+            Field::from(11u128),
+            Field::zero(),
+            Field::zero()
+        ]
+    );
+
+    let mut swapped = two_tokens(40, 6);
+    swapped.assets.swap(0, 1);
+    let err = compute_merge_output(&two_tokens(60, 5), &swapped).expect_err("slots disagree");
+    assert!(err.to_string().contains("slot 0"), "{err}");
+}
@@ -6,7 +6,7 @@ use usernode_circuits::bn254::Field;
 use usernode_circuits::catalog;
 use usernode_circuits::prover::{
     SchnorrEnc, SpendInputEnc, TransferEnc, UtxoEnc, encode_spend_privates, get_circuit,
-    init_default_circuits, prove, verify,
+    init_default_circuits, prove, prove_and_get_outputs, public_outputs, verify,
 };
 
 #[test]
@@ -165,3 +165,19 @@ fn prove_and_verify_utxo_spend() {
     assert_eq!(pis.len(), 1, "expected single public input");
     catalog::clear();
 }
+
+#[test]
+fn prove_and_get_outputs_matches_public_outputs() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let privates = encode_spend_privates(&common::sample_spend_enc());
+    let (proof, outputs) =
+        prove_and_get_outputs("utxo_spend", &privates).expect("prove and read outputs");
+    assert!(verify("utxo_spend", &proof).expect("verify utxo_spend"));
+
+    let expected = public_outputs("utxo_spend", &privates).expect("public outputs");
+    assert_eq!(outputs, expected);
+    catalog::clear();
+}
@@ -1,12 +1,12 @@
 mod common;
 
-use common::{Asset, Keypair, Utxo, serial_guard, spend_digest, utxo_commitment};
+use common::{Asset, Keypair, Utxo, sample_spend_enc, serial_guard, spend_digest, utxo_commitment};
 
 use usernode_circuits::bn254::Field;
 use usernode_circuits::catalog;
 use usernode_circuits::prover::{
     SchnorrEnc, SpendInputEnc, TransferEnc, UtxoEnc, encode_spend_privates, init_default_circuits,
-    prove,
+    prove, prove_with_priv_and_pub,
 };
 
 #[allow(clippy::too_many_arguments)]
@@ -308,3 +308,23 @@ fn insufficient_fee_slot0_rejected() {
     expect_prove_err(&enc);
     catalog::clear();
 }
+
+#[test]
+fn extra_private_input_reports_counts() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let mut privates = encode_spend_privates(&sample_spend_enc());
+    let expected = privates.len();
+    privates.push(usernode_circuits::CircuitFieldElement::from(0u128));
+
+    let err = prove_with_priv_and_pub("utxo_spend", &privates, &[])
+        .expect_err("one private input too many");
+    let msg = err.to_string();
+    assert!(
+        msg.contains(&format!("got {}, expected {expected}", privates.len())),
+        "unexpected error: {msg}"
+    );
+    catalog::clear();
+}
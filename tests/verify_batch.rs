@@ -0,0 +1,42 @@
+//! Batch verification stops at the first proof that does not verify.
+
+mod common;
+
+use common::{sample_spend_enc, serial_guard};
+use usernode_circuits::catalog;
+use usernode_circuits::prover::{
+    encode_spend_privates, init_default_circuits, prove, verify_batch_early_exit,
+};
+
+#[test]
+fn first_invalid_proof_index_is_reported() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let valid =
+        prove("utxo_spend", &encode_spend_privates(&sample_spend_enc())).expect("prove utxo_spend");
+    let mut tampered = valid.clone();
+    if let Some(byte) = tampered.last_mut() {
+        *byte ^= 0x01;
+    }
+
+    assert_eq!(
+        verify_batch_early_exit("utxo_spend", &[&valid, &valid]).expect("batch"),
+        None
+    );
+    assert_eq!(
+        verify_batch_early_exit("utxo_spend", &[&valid, &tampered, &valid]).expect("batch"),
+        Some(1)
+    );
+    assert_eq!(
+        verify_batch_early_exit("utxo_spend", &[&tampered, &valid]).expect("batch"),
+        Some(0)
+    );
+    assert_eq!(
+        verify_batch_early_exit("utxo_spend", &[]).expect("empty batch"),
+        None
+    );
+    assert!(verify_batch_early_exit("no_such_circuit", &[&valid]).is_err());
+    catalog::clear();
+}
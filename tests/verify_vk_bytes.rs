@@ -0,0 +1,33 @@
+//! Verification against raw VK bytes without a registered circuit.
+
+mod common;
+
+use common::{sample_spend_enc, serial_guard};
+use usernode_circuits::catalog;
+use usernode_circuits::prover::{
+    encode_spend_privates, get_circuit, init_default_circuits, prove, verify_with_vk_bytes,
+};
+
+#[test]
+fn verify_with_vk_bytes_skips_catalog() {
+    let _guard = serial_guard();
+    catalog::clear();
+    init_default_circuits().expect("init embedded circuits");
+
+    let privates = encode_spend_privates(&sample_spend_enc());
+    let proof = prove("utxo_spend", &privates).expect("prove utxo_spend");
+    let vk = get_circuit("utxo_spend").expect("spend circuit").vk;
+
+    catalog::remove("utxo_spend").expect("spend circuit registered");
+    assert!(get_circuit("utxo_spend").is_none());
+
+    assert!(verify_with_vk_bytes(&proof, &vk).expect("verify with raw vk"));
+
+    let mut tampered = proof.clone();
+    if let Some(byte) = tampered.last_mut() {
+        *byte ^= 0x01;
+    }
+    assert!(!verify_with_vk_bytes(&tampered, &vk).unwrap_or(false));
+
+    catalog::clear();
+}